@@ -1,62 +1,226 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    time::{Duration, Instant},
+};
+
+use chrono::{Datelike, Local, NaiveDate};
 use rusqlite::Connection;
 
 use crate::{
+    budget::{self, BudgetStatus, Budgets},
     config::load_config,
     db,
+    duplicates::{self, DuplicatePair, DuplicateTolerance, Resolution},
+    export::csv::CsvExportOptions,
+    hooks::{self, Event, Hooks},
+    reports::{self, Report},
+    import::csv::CsvProfile,
    form::TransactionForm,
-    models::{RecurringEntry, Tag, Transaction},
+    models::{Envelope, EnvelopeAssignment, Goal, RecurringEntry, Tag, Transaction, TransactionType},
+    profile,
+    rules::{self, RuleChange, RuleInput, RuleSet},
+    stats::StatsSnapshot,
+    subscriptions::{self, Suggestion},
+    ui::{
+        backup::BackupView, envelopes::EnvelopeView, export::ExportView,
+        goals::GoalView, import::ImportStage,
+    },
 };
 
 #[derive(PartialEq)]
 pub enum Mode {
     Normal,
     Adding,
+    QuickAdd,
     Stats,
+    Forecast,
+    Suggestions,
+    Envelopes,
+    ConfirmOverBudget,
+    Goals,
+    Import,
+    Rules,
+    Duplicates,
+    Export,
+    Backup,
+    Profiles,
+}
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum NotificationLevel {
+    Info,
+    Warning,
+}
+
+// Short-lived message shown on top of the current view
+pub struct Notification {
+    pub message: String,
+    pub level: NotificationLevel,
+    pub expires: Instant,
 }
 
+// How long a notification stays on screen
+const NOTIFICATION_TTL: Duration = Duration::from_secs(6);
+
+// How long a transaction added from outside stays marked as new
+const HIGHLIGHT_TTL: Duration = Duration::from_secs(10);
+
 pub struct App {
     pub mode: Mode,
     pub form: TransactionForm,
     // When Some(id) we're editing an existing transaction
     pub editing: Option<i32>,
+    // Line typed into the quick-add prompt, parsed as it changes
+    pub quick_add: String,
 
     // Tags loaded from YAML config
     pub tags: Vec<Tag>,
 
     pub transactions: Vec<Transaction>,
     pub recurring_entries: Vec<RecurringEntry>,
+    pub envelopes: Vec<Envelope>,
+    pub envelope_assignments: Vec<EnvelopeAssignment>,
+    pub envelope_view: EnvelopeView,
+    pub goals: Vec<Goal>,
+    pub goal_view: GoalView,
+    pub import: ImportStage,
+    // CSV mappings from YAML config, in name order
+    pub import_profiles: Vec<(String, CsvProfile)>,
+    pub ofx_accounts: BTreeMap<String, String>,
+    pub export: ExportView,
+    pub csv_export: CsvExportOptions,
+    pub backup: BackupView,
+
+    // Categorisation rules from YAML config
+    pub rules: RuleSet,
+    pub apply_rules_to_manual: bool,
+    // Changes the rules would make to stored transactions, awaiting confirmation
+    pub rule_changes: Vec<RuleChange>,
+    pub rule_selected: usize,
+
+    // Likely duplicate pairs under review
+    pub duplicate_tolerance: DuplicateTolerance,
+    pub duplicates: Vec<DuplicatePair>,
+    pub duplicate_selected: usize,
+
+    // Active profile and the switcher's state; `switch_profile` is picked up by the event loop
+    pub profile: String,
+    pub profiles: Vec<String>,
+    pub profile_selected: usize,
+    pub new_profile: String,
+    pub switch_profile: Option<String>,
+
     pub selected: usize,
     pub currency: String,
+
+    // Monthly spending limits from YAML config
+    pub budgets: Budgets,
+    // Budgets the entry in the form would break, shown while confirming
+    pub pending_breaches: Vec<BudgetStatus>,
+
+    // Number of days projected by the forecast view
+    pub forecast_days: i64,
+
+    // Recurring entries detected from transaction history, awaiting review
+    pub suggestions: Vec<Suggestion>,
+    pub suggestion_selected: usize,
+
+    // Date the app last ran recurring generation for; used to detect rollover
+    pub today: NaiveDate,
+    pub notification: Option<Notification>,
+
+    // Shell commands run on data events
+    pub hooks: Hooks,
+
+    // Output of the report scripts, shown in the stats view; index 0 is the built-in details
+    pub reports: Vec<Report>,
+    pub report_index: usize,
+
+    // Last seen `PRAGMA data_version`; a change means another process wrote to the database
+    pub data_version: i64,
+    // Transaction added from outside, marked in the list until the instant passes
+    pub highlight: Option<(i32, Instant)>,
 }
 
 impl App {
     pub fn new(conn: &Connection) -> Self {
         let config = load_config();
+        let budgets = Budgets::from_config(&config);
+        let import_profiles = config.import_profiles.clone().into_iter().collect();
+        let (rules, rule_errors) = RuleSet::new(&config.rules);
 
         let tags: Vec<Tag> = config
             .tags
             .into_iter()
-            .map(|s| Tag::from_str(&s))
+            .map(|s| Tag::new(&s))
             .collect();
 
         let transactions = db::get_transactions(conn).unwrap_or_default();
         let recurring_entries = db::get_recurring_entries(conn).unwrap_or_default();
+        let envelopes = db::get_envelopes(conn).unwrap_or_default();
+        let envelope_assignments = db::get_envelope_assignments(conn).unwrap_or_default();
+        let goals = db::get_goals(conn).unwrap_or_default();
 
-        Self {
+        let mut app = Self {
             mode: Mode::Normal,
             form: TransactionForm::new(),
             editing: None,
+            quick_add: String::new(),
             tags,
             transactions,
             recurring_entries,
+            envelopes,
+            envelope_assignments,
+            envelope_view: EnvelopeView::new(),
+            goals,
+            goal_view: GoalView::new(),
+            import: ImportStage::new(),
+            import_profiles,
+            ofx_accounts: config.ofx_accounts.clone(),
+            export: ExportView::new(),
+            csv_export: config.csv_export.clone(),
+            backup: BackupView::new(),
+            rules,
+            apply_rules_to_manual: config.apply_rules_to_manual,
+            rule_changes: Vec::new(),
+            rule_selected: 0,
+            duplicate_tolerance: config.duplicates.clone(),
+            duplicates: Vec::new(),
+            duplicate_selected: 0,
+            profile: profile::active().profile,
+            profiles: Vec::new(),
+            profile_selected: 0,
+            new_profile: String::new(),
+            switch_profile: None,
             selected: 0,
             currency: config.currency,
+            budgets,
+            pending_breaches: Vec::new(),
+            forecast_days: crate::forecast::HORIZONS[0],
+            suggestions: Vec::new(),
+            suggestion_selected: 0,
+            today: Local::now().date_naive(),
+            notification: None,
+            hooks: config.hooks,
+            reports: Vec::new(),
+            report_index: 0,
+            data_version: db::data_version(conn).unwrap_or_default(),
+            highlight: None,
+        };
+
+        if !rule_errors.is_empty() {
+            app.notify(rule_errors.join("; "), NotificationLevel::Warning);
         }
+
+        app
     }
 
     pub fn refresh(&mut self, conn: &Connection) {
         self.transactions = db::get_transactions(conn).unwrap_or_default();
         self.recurring_entries = db::get_recurring_entries(conn).unwrap_or_default();
+        self.envelopes = db::get_envelopes(conn).unwrap_or_default();
+        self.envelope_assignments = db::get_envelope_assignments(conn).unwrap_or_default();
+        self.goals = db::get_goals(conn).unwrap_or_default();
 
         // Clamp selection if list shrinks
         if self.selected >= self.transactions.len() && self.selected > 0 {
@@ -64,14 +228,115 @@ impl App {
         }
     }
 
-    pub fn save_transaction(&mut self, conn: &Connection) {
+    /// Pick up tags and currency after config.yaml was rewritten
+    pub fn reload_config(&mut self) {
+        let config = load_config();
+        self.tags = config.tags.iter().map(|s| Tag::new(s)).collect();
+        self.currency = config.currency;
+        self.hooks = config.hooks;
+    }
+
+    /// Save the form, asking for confirmation first when a hard budget limit would be broken
+    pub fn submit_form(&mut self, conn: &Connection) {
+        if self.apply_rules_to_manual && self.editing.is_none() {
+            self.apply_rules_to_form();
+        }
+
+        let breaches = self.form_budget_breaches();
+
+        if self.budgets.hard_limit && !breaches.is_empty() {
+            self.pending_breaches = breaches;
+            self.mode = Mode::ConfirmOverBudget;
+            return;
+        }
+
+        self.save_transaction(conn);
+        self.form.reset();
+        self.mode = Mode::Normal;
+    }
+
+    /// Let the first matching rule fill in tag, source and notes of a new entry
+    fn apply_rules_to_form(&mut self) {
         let amount: f64 = self.form.amount.trim().parse().unwrap_or(0.0);
+        let input = RuleInput {
+            source: &self.form.source,
+            amount,
+            kind: self.form.kind,
+            account: None,
+        };
+        let Some(outcome) = self.rules.apply(&input, &self.tags) else {
+            return;
+        };
 
-        let tag = self
-            .tags
+        if let Some(index) = outcome
+            .tag
+            .and_then(|tag| self.tags.iter().position(|t| *t == tag))
+        {
+            self.form.tag_index = index;
+        }
+        if let Some(source) = outcome.source {
+            self.form.source = source;
+        }
+        if let Some(notes) = outcome.notes {
+            self.form.notes = notes;
+        }
+    }
+
+    fn form_tag(&self) -> Tag {
+        self.tags
             .get(self.form.tag_index)
             .unwrap_or(&Tag("other".into()))
-            .clone();
+            .clone()
+    }
+
+    fn form_budget_breaches(&self) -> Vec<BudgetStatus> {
+        if self.form.kind != TransactionType::Debit {
+            return Vec::new();
+        }
+
+        let amount: f64 = self.form.amount.trim().parse().unwrap_or(0.0);
+        let date = NaiveDate::parse_from_str(&self.form.date, "%Y-%m-%d").unwrap_or(self.today);
+
+        budget::check_entry(
+            &self.transactions,
+            &self.budgets,
+            &self.form_tag(),
+            amount,
+            date,
+            self.editing,
+        )
+    }
+
+    pub fn save_transaction(&mut self, conn: &Connection) {
+        let amount: f64 = self.form.amount.trim().parse().unwrap_or(0.0);
+        let tag = self.form_tag();
+
+        // Checked before writing so the new entry isn't counted twice
+        let breaches = self.form_budget_breaches();
+        self.pending_breaches.clear();
+
+        let duplicate = match self.editing {
+            Some(_) => None,
+            None => duplicates::find_match(
+                &self.transactions,
+                &self.form.source,
+                amount,
+                self.form.kind,
+                &self.form.date,
+                &self.duplicate_tolerance,
+            )
+            .map(|tx| format!("Looks like a duplicate of {} on {}, press D to review", tx.source, tx.date)),
+        };
+
+        let mut saved = Transaction {
+            id: 0,
+            source: self.form.source.clone(),
+            amount,
+            kind: self.form.kind,
+            tag: tag.clone(),
+            date: self.form.date.clone(),
+            notes: self.form.notes.clone(),
+        };
 
         if let Some(id) = self.editing {
             db::update_transaction(
@@ -85,6 +350,12 @@ impl App {
             )
             .unwrap();
             self.editing = None;
+
+            if let Some(before) = self.transactions.iter().find(|tx| tx.id == id).cloned() {
+                saved.id = id;
+                saved.notes = before.notes.clone();
+                self.fire_hook(&Event::TransactionUpdated { before: &before, after: &saved });
+            }
         } else {
             db::add_transaction(
                 conn,
@@ -95,20 +366,47 @@ impl App {
                 &self.form.date,
             )
             .unwrap();
+            saved.id = conn.last_insert_rowid() as i32;
+
+            if !self.form.notes.is_empty() {
+                db::set_transaction_notes(conn, conn.last_insert_rowid(), &self.form.notes).unwrap();
+            }
+            self.fire_hook(&Event::TransactionAdded(&saved));
 
             // If marked as recurring, also add to recurring_entries
             if self.form.recurring {
+                let date = NaiveDate::parse_from_str(&self.form.date, "%Y-%m-%d")
+                    .unwrap_or(self.today);
+
                 db::add_recurring_entry(
                     conn,
                     &self.form.source,
                     amount,
                     self.form.kind,
                     &tag,
+                    date.day(),
+                    // This month's occurrence is the transaction we just saved
+                    &date.format("%Y-%m").to_string(),
                 )
                 .unwrap();
             }
         }
 
+        if !breaches.is_empty() {
+            // Logged and sent to hooks once, when the limit is first crossed
+            let month = self.form.date.get(..7).unwrap_or_default().to_string();
+            for b in breaches.iter().filter(|b| b.exceeded_now) {
+                db::add_budget_event(conn, &month, b.tag.as_ref(), b.limit, b.spent, &self.form.source)
+                    .unwrap();
+                self.fire_hook(&Event::BudgetExceeded { status: b, transaction: &saved });
+            }
+
+            let message = budget::describe_breaches(&breaches, &self.currency);
+            self.notify(message, NotificationLevel::Warning);
+        } else if let Some(message) = duplicate {
+            self.notify(message, NotificationLevel::Warning);
+        }
+
         self.refresh(conn);
     }
 
@@ -140,9 +438,230 @@ impl App {
             return;
         }
 
-        let id = self.transactions[self.selected].id;
-        db::delete_transaction(conn, id).unwrap();
+        let tx = self.transactions[self.selected].clone();
+        db::delete_transaction(conn, tx.id).unwrap();
+        self.fire_hook(&Event::TransactionDeleted(&tx));
 
         self.refresh(conn);
     }
+
+    pub fn open_suggestions(&mut self) {
+        self.suggestions = subscriptions::detect_subscriptions(
+            &self.transactions,
+            &self.recurring_entries,
+        );
+        self.suggestion_selected = 0;
+        self.mode = Mode::Suggestions;
+    }
+
+    /// Dry run the rules over every stored transaction and show the diff
+    pub fn open_rules(&mut self) {
+        self.rule_changes = rules::plan_changes(&self.rules, &self.transactions, &self.tags);
+        self.rule_selected = 0;
+        self.mode = Mode::Rules;
+    }
+
+    pub fn open_stats(&mut self) {
+        self.run_reports();
+        self.mode = Mode::Stats;
+    }
+
+    /// Re-run the report scripts against the current data
+    pub fn run_reports(&mut self) {
+        let snapshot = StatsSnapshot::new(&self.transactions, &self.budgets, &self.goals);
+        self.reports = reports::run_all(&self.transactions, &snapshot, &self.currency);
+        self.report_index = self.report_index.min(self.reports.len());
+    }
+
+    pub fn open_profiles(&mut self) {
+        self.profiles = profile::list_profiles();
+        self.profile_selected = self
+            .profiles
+            .iter()
+            .position(|p| *p == self.profile)
+            .unwrap_or(0);
+        self.new_profile.clear();
+        self.mode = Mode::Profiles;
+    }
+
+    pub fn open_duplicates(&mut self, conn: &Connection) {
+        self.find_duplicates(conn);
+        self.duplicate_selected = 0;
+        self.mode = Mode::Duplicates;
+    }
+
+    fn find_duplicates(&mut self, conn: &Connection) {
+        let dismissed: HashSet<(i32, i32)> = db::get_duplicate_dismissals(conn)
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+
+        self.duplicates =
+            duplicates::find_duplicates(&self.transactions, &self.duplicate_tolerance, &dismissed);
+
+        if self.duplicate_selected >= self.duplicates.len() && self.duplicate_selected > 0 {
+            self.duplicate_selected = self.duplicates.len().saturating_sub(1);
+        }
+    }
+
+    pub fn resolve_duplicate(&mut self, conn: &Connection, resolution: Resolution) {
+        let Some(pair) = self.duplicates.get(self.duplicate_selected).cloned() else {
+            return;
+        };
+
+        match duplicates::resolve(conn, &pair, resolution, &self.hooks) {
+            Ok(errors) => self.report_hook_errors(&errors),
+            Err(e) => self.notify(format!("Resolving the duplicate failed: {}", e), NotificationLevel::Warning),
+        }
+
+        // Deleting one side can resolve other pairs too, so detect again
+        self.refresh(conn);
+        self.find_duplicates(conn);
+    }
+
+    pub fn accept_suggestion(&mut self, conn: &Connection) {
+        if self.suggestions.is_empty() {
+            return;
+        }
+
+        let suggestion = self.suggestions.remove(self.suggestion_selected);
+
+        // The latest occurrence is already in the ledger, so mark its month as posted
+        db::add_recurring_entry(
+            conn,
+            &suggestion.source,
+            suggestion.amount,
+            suggestion.kind,
+            &suggestion.tag,
+            suggestion.day,
+            &suggestion.last_month,
+        )
+        .unwrap();
+
+        self.clamp_suggestion_selection();
+        self.refresh(conn);
+    }
+
+    pub fn dismiss_suggestion(&mut self) {
+        if self.suggestions.is_empty() {
+            return;
+        }
+
+        self.suggestions.remove(self.suggestion_selected);
+        self.clamp_suggestion_selection();
+    }
+
+    fn clamp_suggestion_selection(&mut self) {
+        if self.suggestion_selected >= self.suggestions.len() && self.suggestion_selected > 0 {
+            self.suggestion_selected -= 1;
+        }
+    }
+
+    /// Called on every pass of the event loop. When the local date changes
+    /// (midnight, or the 1st of a month) recurring entries are generated again
+    /// so a long-running session doesn't miss occurrences.
+    pub fn tick(&mut self, conn: &Connection) {
+        if self.notification.as_ref().is_some_and(|n| Instant::now() >= n.expires) {
+            self.notification = None;
+        }
+
+        if self.highlight.is_some_and(|(_, until)| Instant::now() >= until) {
+            self.highlight = None;
+        }
+
+        self.check_external_changes(conn);
+
+        let today = Local::now().date_naive();
+        if today == self.today {
+            return;
+        }
+
+        // Keep an untouched default date in an open form in step with the clock
+        let stale_default = self.today.format("%Y-%m-%d").to_string();
+        if self.form.date == stale_default {
+            self.form.date = today.format("%Y-%m-%d").to_string();
+        }
+        self.today = today;
+
+        match db::insert_recurring_for_month(conn, today) {
+            Ok(posted) => self.report_recurring(&posted),
+            Err(e) => self.notify(
+                format!("Recurring generation failed: {}", e),
+                NotificationLevel::Warning,
+            ),
+        }
+
+        self.refresh(conn);
+    }
+
+    /// Reload when another process committed to the database, e.g. `fitui add`
+    /// from a script, and mark the newest added transaction
+    fn check_external_changes(&mut self, conn: &Connection) {
+        let Ok(version) = db::data_version(conn) else {
+            return;
+        };
+        if version == self.data_version {
+            return;
+        }
+        self.data_version = version;
+
+        let known: HashSet<i32> = self.transactions.iter().map(|tx| tx.id).collect();
+        let selected_id = self.transactions.get(self.selected).map(|tx| tx.id);
+        self.refresh(conn);
+
+        let added: Vec<&Transaction> = self
+            .transactions
+            .iter()
+            .filter(|tx| !known.contains(&tx.id))
+            .collect();
+
+        // Jump to the new row from the main list; elsewhere keep the selection in place
+        let newest = added.iter().max_by_key(|tx| tx.id).map(|tx| tx.id);
+        let focus = if self.mode == Mode::Normal { newest.or(selected_id) } else { selected_id };
+        if let Some(index) = focus.and_then(|id| self.transactions.iter().position(|tx| tx.id == id)) {
+            self.selected = index;
+        }
+
+        if let Some(id) = newest {
+            let names: Vec<&str> = added.iter().map(|tx| tx.source.as_str()).collect();
+            let message = format!("↻ {} new from outside: {}", added.len(), names.join(", "));
+
+            self.highlight = Some((id, Instant::now() + HIGHLIGHT_TTL));
+            self.notify(message, NotificationLevel::Info);
+        }
+    }
+
+    pub fn report_recurring(&mut self, posted: &[Transaction]) {
+        if posted.is_empty() {
+            return;
+        }
+        self.fire_hook(&Event::RecurringPosted(posted));
+
+        let names: Vec<&str> = posted.iter().map(|tx| tx.source.as_str()).collect();
+        self.notify(
+            format!("🔄 Posted {} recurring: {}", posted.len(), names.join(", ")),
+            NotificationLevel::Info,
+        );
+    }
+
+    /// Run the hooks configured for an event; hooks that fail to start show a warning
+    fn fire_hook(&mut self, event: &Event) {
+        let errors = hooks::fire(&self.hooks, event);
+        self.report_hook_errors(&errors);
+    }
+
+    /// Show hooks that failed to start, replacing the current notification
+    pub fn report_hook_errors(&mut self, errors: &[String]) {
+        if !errors.is_empty() {
+            self.notify(errors.join("; "), NotificationLevel::Warning);
+        }
+    }
+
+    pub fn notify(&mut self, message: String, level: NotificationLevel) {
+        self.notification = Some(Notification {
+            message,
+            level,
+            expires: Instant::now() + NOTIFICATION_TTL,
+        });
+    }
 }
@@ -0,0 +1,408 @@
+use std::{ collections::{ HashMap, HashSet }, fs, path::Path };
+
+use chrono::Local;
+use rusqlite::Connection;
+use serde::{ Deserialize, Serialize };
+use sha2::{ Digest, Sha256 };
+
+use crate::{
+    config::Config,
+    db,
+    hooks::{ self, Event },
+    models::{ Envelope, EnvelopeAssignment, Goal, RecurringEntry, Transaction },
+};
+
+/// Identifies FiTui backups among other JSON files
+const FORMAT: &str = "fitui-backup";
+
+/// Bumped whenever `BackupData` changes incompatibly
+pub const VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Backup {
+    pub format: String,
+    pub version: u32,
+    pub created_at: String,
+    // "sha256:<hex>" of the compact JSON of `data`
+    pub checksum: String,
+    pub data: BackupData,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupData {
+    pub currency: String,
+    pub tags: Vec<String>,
+    pub transactions: Vec<Transaction>,
+    pub recurring_entries: Vec<RecurringEntry>,
+    #[serde(default)]
+    pub envelopes: Vec<Envelope>,
+    #[serde(default)]
+    pub envelope_assignments: Vec<EnvelopeAssignment>,
+    #[serde(default)]
+    pub goals: Vec<Goal>,
+    #[serde(default)]
+    pub imported_ids: Vec<ImportedId>,
+}
+
+/// Statement id remembered by OFX imports
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportedId {
+    pub account: String,
+    pub external_id: String,
+    pub transaction_id: i64,
+}
+
+/// What to do with data already in the database
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RestoreMode {
+    // Keep existing rows; anything already present is skipped
+    Merge,
+    // Delete everything first
+    Replace,
+}
+
+#[derive(Debug, Default)]
+pub struct RestoreSummary {
+    pub transactions: usize,
+    pub skipped_transactions: usize,
+    pub recurring_entries: usize,
+    pub envelopes: usize,
+    pub goals: usize,
+    pub added_tags: Vec<String>,
+    // Backup currency when it differs from the current one and wasn't applied
+    pub currency_conflict: Option<String>,
+    // Tags or currency were merged into the config, which needs saving
+    pub config_changed: bool,
+    // Hooks that failed to start
+    pub warnings: Vec<String>,
+}
+
+impl RestoreSummary {
+    pub fn describe(&self) -> String {
+        let mut text = format!(
+            "Restored {} transactions ({} already present), {} recurring, {} envelopes, {} goals",
+            self.transactions,
+            self.skipped_transactions,
+            self.recurring_entries,
+            self.envelopes,
+            self.goals
+        );
+        if !self.added_tags.is_empty() {
+            text.push_str(&format!("; added tags {}", self.added_tags.join(", ")));
+        }
+        if let Some(currency) = &self.currency_conflict {
+            text.push_str(&format!("; backup currency {} was not applied", currency));
+        }
+        text
+    }
+}
+
+// ============================================================================
+// Writing and validating backups
+// ============================================================================
+
+/// Snapshot the database plus tags and currency from config
+pub fn create_backup(conn: &Connection, config: &Config) -> rusqlite::Result<Backup> {
+    let data = BackupData {
+        currency: config.currency.clone(),
+        tags: config.tags.clone(),
+        transactions: db::get_transactions(conn)?,
+        recurring_entries: db::get_recurring_entries(conn)?,
+        envelopes: db::get_envelopes(conn)?,
+        envelope_assignments: db::get_envelope_assignments(conn)?,
+        goals: db::get_goals(conn)?,
+        imported_ids: db::get_imported_ids(conn)?
+            .into_iter()
+            .map(|(account, external_id, transaction_id)| ImportedId {
+                account,
+                external_id,
+                transaction_id,
+            })
+            .collect(),
+    };
+
+    let value = serde_json::to_value(&data).expect("backup data is always valid JSON");
+
+    Ok(Backup {
+        format: FORMAT.into(),
+        version: VERSION,
+        created_at: Local::now().to_rfc3339(),
+        checksum: checksum(&value),
+        data,
+    })
+}
+
+pub fn write_backup(conn: &Connection, config: &Config, path: &Path) -> Result<usize, String> {
+    let backup = create_backup(conn, config).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(&backup).map_err(|e| e.to_string())?;
+
+    fs::write(path, json).map_err(|e| format!("Could not write {}: {}", path.display(), e))?;
+    Ok(backup.data.transactions.len())
+}
+
+/// Parse a backup file, checking its format, version and checksum
+pub fn read_backup(path: &Path) -> Result<Backup, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+    let value: serde_json::Value = serde_json::from_str(&text).map_err(|e| format!("{} is not valid JSON: {}", path.display(), e))?;
+
+    if value.get("format").and_then(|f| f.as_str()) != Some(FORMAT) {
+        return Err(format!("{} is not a FiTui backup", path.display()));
+    }
+
+    let version = value
+        .get("version")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0);
+    if version == 0 || version > (VERSION as u64) {
+        return Err(format!("Unsupported backup version {} (this FiTui reads up to {})", version, VERSION));
+    }
+
+    let expected = value
+        .get("checksum")
+        .and_then(|c| c.as_str())
+        .unwrap_or_default();
+    let actual = checksum(value.get("data").unwrap_or(&serde_json::Value::Null));
+    if expected != actual {
+        return Err("Backup checksum does not match, the file is damaged or was edited".into());
+    }
+
+    serde_json::from_value(value).map_err(|e| format!("Invalid backup contents: {}", e))
+}
+
+/// Hash of the compact JSON. Objects are serialised with sorted keys, so
+/// the result doesn't depend on field order in the file. Amounts must parse
+/// back to the exact same f64, which is why serde_json's `float_roundtrip`
+/// feature is enabled.
+fn checksum(data: &serde_json::Value) -> String {
+    let digest = Sha256::digest(data.to_string().as_bytes());
+    let hex: String = digest
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    format!("sha256:{}", hex)
+}
+
+// ============================================================================
+// Restoring
+// ============================================================================
+
+/// Load a validated backup in a single SQLite transaction. In merge mode a
+/// transaction with the same date, source, amount and type as an existing one
+/// is treated as already present; envelopes and goals are matched by name.
+/// The backup's tags and currency are merged into `config`, which the caller
+/// saves when `config_changed` is set.
+pub fn restore(
+    conn: &Connection,
+    backup: &Backup,
+    mode: RestoreMode,
+    config: &mut Config
+) -> Result<RestoreSummary, String> {
+    let data = &backup.data;
+    let mut summary = RestoreSummary::default();
+
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    let previous = db::get_transactions(&tx).map_err(|e| e.to_string())?;
+    let was_empty = previous.is_empty();
+
+    let removed = if mode == RestoreMode::Replace {
+        db::clear_all_data(&tx).map_err(|e| e.to_string())?;
+        previous
+    } else {
+        Vec::new()
+    };
+
+    let added = restore_rows(&tx, data, &mut summary).map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    let events: Vec<Event> = removed
+        .iter()
+        .map(Event::TransactionDeleted)
+        .chain(added.iter().map(Event::TransactionAdded))
+        .collect();
+    summary.warnings = hooks::fire_all(&config.hooks, &events);
+
+    // Tags and currency live in config.yaml rather than SQLite
+    for tag in &data.tags {
+        if !config.tags.contains(tag) {
+            config.tags.push(tag.clone());
+            summary.added_tags.push(tag.clone());
+            summary.config_changed = true;
+        }
+    }
+
+    if data.currency != config.currency {
+        if was_empty || mode == RestoreMode::Replace {
+            config.currency = data.currency.clone();
+            summary.config_changed = true;
+        } else {
+            summary.currency_conflict = Some(data.currency.clone());
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Insert the backup's rows, returning the transactions added
+fn restore_rows(conn: &Connection, data: &BackupData, summary: &mut RestoreSummary) -> rusqlite::Result<Vec<Transaction>> {
+    let existing = db::get_transactions(conn)?;
+    let same = |a: &Transaction, b: &Transaction| {
+        a.date == b.date && a.source == b.source && a.kind == b.kind && (a.amount - b.amount).abs() < 0.005
+    };
+
+    // Backup transaction id -> id in this database
+    let mut transaction_ids: HashMap<i64, i64> = HashMap::new();
+    // Each existing row stands in for one backup row, so two identical coffees
+    // in the backup against one in the database still restore the second
+    let mut matched: HashSet<i32> = HashSet::new();
+    let mut added = Vec::new();
+
+    for t in &data.transactions {
+        if let Some(found) = existing.iter().find(|e| !matched.contains(&e.id) && same(e, t)) {
+            matched.insert(found.id);
+            transaction_ids.insert(t.id as i64, found.id as i64);
+            summary.skipped_transactions += 1;
+            continue;
+        }
+
+        db::add_transaction(conn, &t.source, t.amount, t.kind, &t.tag, &t.date)?;
+        let id = conn.last_insert_rowid();
+        if !t.notes.is_empty() {
+            db::set_transaction_notes(conn, id, &t.notes)?;
+        }
+        transaction_ids.insert(t.id as i64, id);
+        added.push(Transaction { id: id as i32, ..t.clone() });
+        summary.transactions += 1;
+    }
+
+    for imported in &data.imported_ids {
+        if let Some(&id) = transaction_ids.get(&imported.transaction_id) {
+            db::mark_imported(conn, &imported.account, &imported.external_id, id)?;
+        }
+    }
+
+    let recurring = db::get_recurring_entries(conn)?;
+    for entry in &data.recurring_entries {
+        let present = recurring
+            .iter()
+            .any(|r| {
+                r.source == entry.source &&
+                    r.kind == entry.kind &&
+                    r.day == entry.day &&
+                    (r.amount - entry.amount).abs() < 0.005
+            });
+        if present {
+            continue;
+        }
+
+        db::add_recurring_entry(
+            conn,
+            &entry.source,
+            entry.amount,
+            entry.kind,
+            &entry.tag,
+            entry.day,
+            &entry.last_inserted_month
+        )?;
+        if !entry.active {
+            db::toggle_recurring_entry(conn, conn.last_insert_rowid() as i32, false)?;
+        }
+        summary.recurring_entries += 1;
+    }
+
+    // Backup envelope id -> id in this database
+    let mut envelope_ids: HashMap<i32, i32> = HashMap::new();
+    let envelopes = db::get_envelopes(conn)?;
+    for envelope in &data.envelopes {
+        if let Some(found) = envelopes.iter().find(|e| e.name == envelope.name) {
+            envelope_ids.insert(envelope.id, found.id);
+            continue;
+        }
+
+        db::add_envelope(conn, &envelope.name, &envelope.tag)?;
+        envelope_ids.insert(envelope.id, conn.last_insert_rowid() as i32);
+        summary.envelopes += 1;
+    }
+
+    // Assignments already made here win over the backup
+    let assignments = db::get_envelope_assignments(conn)?;
+    for assignment in &data.envelope_assignments {
+        let Some(&envelope_id) = envelope_ids.get(&assignment.envelope_id) else {
+            continue;
+        };
+        let present = assignments
+            .iter()
+            .any(|a| a.envelope_id == envelope_id && a.month == assignment.month);
+        if !present {
+            db::set_envelope_assignment(conn, envelope_id, &assignment.month, assignment.amount)?;
+        }
+    }
+
+    let goals = db::get_goals(conn)?;
+    for goal in &data.goals {
+        if goals.iter().any(|g| g.name == goal.name) {
+            continue;
+        }
+
+        db::add_goal(conn, &goal.name, goal.target, &goal.target_date, &goal.tag)?;
+        summary.goals += 1;
+    }
+
+    Ok(added)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ Tag, TransactionType };
+
+    #[test]
+    fn backup_round_trips_amounts_that_are_not_exact_in_binary() {
+        let conn = db::open_db(Path::new(":memory:")).unwrap();
+        let amounts = [0.1 + 0.2, 1.1 + 0.03];
+        for amount in amounts {
+            db::add_transaction(&conn, "Split bill", amount, TransactionType::Debit, &Tag::new("food"), "2026-10-01").unwrap();
+        }
+
+        let path = std::env::temp_dir().join(format!("fitui-backup-test-{}.json", std::process::id()));
+        write_backup(&conn, &Config::default(), &path).unwrap();
+        let backup = read_backup(&path);
+        fs::remove_file(&path).unwrap();
+
+        let backup = backup.unwrap();
+        let restored = db::open_db(Path::new(":memory:")).unwrap();
+        restore(&restored, &backup, RestoreMode::Replace, &mut Config::default()).unwrap();
+
+        let mut restored_amounts: Vec<f64> = db
+            ::get_transactions(&restored)
+            .unwrap()
+            .iter()
+            .map(|tx| tx.amount)
+            .collect();
+        restored_amounts.sort_by(f64::total_cmp);
+        assert_eq!(restored_amounts, amounts);
+    }
+
+    #[test]
+    fn merge_matches_each_existing_transaction_once() {
+        let food = Tag::new("food");
+        let source = db::open_db(Path::new(":memory:")).unwrap();
+        for _ in 0..2 {
+            db::add_transaction(&source, "Coffee", 5.0, TransactionType::Debit, &food, "2026-10-01").unwrap();
+        }
+        let backup = create_backup(&source, &Config::default()).unwrap();
+
+        let conn = db::open_db(Path::new(":memory:")).unwrap();
+        db::add_transaction(&conn, "Coffee", 5.0, TransactionType::Debit, &food, "2026-10-01").unwrap();
+
+        let summary = restore(&conn, &backup, RestoreMode::Merge, &mut Config::default()).unwrap();
+        assert_eq!(summary.skipped_transactions, 1);
+        assert_eq!(summary.transactions, 1);
+        assert_eq!(db::get_transactions(&conn).unwrap().len(), 2);
+
+        // Merging the same backup again adds nothing
+        let summary = restore(&conn, &backup, RestoreMode::Merge, &mut Config::default()).unwrap();
+        assert_eq!(summary.skipped_transactions, 2);
+        assert_eq!(summary.transactions, 0);
+        assert_eq!(db::get_transactions(&conn).unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+
+use chrono::{ Datelike, Duration, NaiveDate };
+use serde::Serialize;
+
+use crate::{ config::Config, models::{ Tag, Transaction, TransactionType } };
+
+/// Share of a budget at which it is shown as "close to the limit"
+pub const WARNING_RATIO: f64 = 0.8;
+
+/// Monthly spending limits loaded from config.yaml
+#[derive(Debug, Clone, Default)]
+pub struct Budgets {
+    pub per_tag: HashMap<Tag, f64>,
+    pub overall: Option<f64>,
+
+    // Ask for confirmation before saving an entry that breaks a limit
+    pub hard_limit: bool,
+}
+
+impl Budgets {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            per_tag: config.budgets
+                .iter()
+                .map(|(tag, limit)| (Tag::new(tag), *limit))
+                .collect(),
+            overall: config.monthly_budget,
+            hard_limit: config.budget_hard_limit,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BudgetStatus {
+    pub tag: Option<Tag>, // None for the overall monthly budget
+    pub limit: f64,
+    pub spent: f64,
+    // Set by `check_entry` when the entry itself took spending over the limit,
+    // rather than adding to a budget that was already exceeded
+    pub exceeded_now: bool,
+}
+
+impl BudgetStatus {
+    pub fn ratio(&self) -> f64 {
+        if self.limit > 0.0 { self.spent / self.limit } else { 0.0 }
+    }
+
+    pub fn remaining(&self) -> f64 {
+        self.limit - self.spent
+    }
+
+    pub fn label(&self) -> String {
+        match &self.tag {
+            Some(tag) => format!("#{}", tag.as_str()),
+            None => "Overall".to_string(),
+        }
+    }
+}
+
+/// Budget vs actual for a single month
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BudgetReport {
+    pub month: String, // Format: "YYYY-MM"
+    pub overall: Option<BudgetStatus>,
+    pub tags: Vec<BudgetStatus>,
+    pub days_left: i64, // Including today
+}
+
+impl BudgetReport {
+    pub fn new(transactions: &[Transaction], budgets: &Budgets, today: NaiveDate) -> Self {
+        let month = today.format("%Y-%m").to_string();
+        let month_spent_per_tag = calculate_month_spent_per_tag(transactions, &month);
+        let month_spent: f64 = month_spent_per_tag.values().sum();
+
+        let overall = budgets.overall.map(|limit| BudgetStatus {
+            tag: None,
+            limit,
+            spent: month_spent,
+            exceeded_now: false,
+        });
+
+        let mut tags: Vec<BudgetStatus> = budgets.per_tag
+            .iter()
+            .map(|(tag, limit)| BudgetStatus {
+                tag: Some(tag.clone()),
+                limit: *limit,
+                spent: month_spent_per_tag.get(tag).copied().unwrap_or(0.0),
+                exceeded_now: false,
+            })
+            .collect();
+        tags.sort_by(|a, b| b.ratio().partial_cmp(&a.ratio()).unwrap_or(std::cmp::Ordering::Equal));
+
+        Self {
+            month,
+            overall,
+            tags,
+            days_left: days_left_in_month(today),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.overall.is_none() && self.tags.is_empty()
+    }
+
+    pub fn for_tag(&self, tag: &Tag) -> Option<&BudgetStatus> {
+        self.tags.iter().find(|s| s.tag.as_ref() == Some(tag))
+    }
+
+    /// What can still be spent per day without going over the overall budget
+    pub fn remaining_per_day(&self) -> Option<f64> {
+        self.overall
+            .as_ref()
+            .map(|s| s.remaining().max(0.0) / (self.days_left.max(1) as f64))
+    }
+}
+
+/// Budgets that would be over their limit after a debit of `amount` tagged `tag`
+/// on `date`, including ones already exceeded before it (`exceeded_now` is false
+/// for those). `replacing` is the id of a transaction being edited, whose old
+/// amount no longer counts.
+pub fn check_entry(
+    transactions: &[Transaction],
+    budgets: &Budgets,
+    tag: &Tag,
+    amount: f64,
+    date: NaiveDate,
+    replacing: Option<i32>
+) -> Vec<BudgetStatus> {
+    let month = date.format("%Y-%m").to_string();
+    let others: Vec<Transaction> = transactions
+        .iter()
+        .filter(|tx| Some(tx.id) != replacing)
+        .cloned()
+        .collect();
+
+    let per_tag = calculate_month_spent_per_tag(&others, &month);
+    let tag_before = per_tag.get(tag).copied().unwrap_or(0.0);
+    let month_before: f64 = per_tag.values().sum();
+
+    let mut breaches = Vec::new();
+
+    if let Some(limit) = budgets.per_tag.get(tag) && tag_before + amount > *limit {
+        breaches.push(BudgetStatus {
+            tag: Some(tag.clone()),
+            limit: *limit,
+            spent: tag_before + amount,
+            exceeded_now: tag_before <= *limit,
+        });
+    }
+
+    if let Some(limit) = budgets.overall && month_before + amount > limit {
+        breaches.push(BudgetStatus {
+            tag: None,
+            limit,
+            spent: month_before + amount,
+            exceeded_now: month_before <= limit,
+        });
+    }
+
+    breaches
+}
+
+/// Human readable summary of exceeded budgets for notifications
+pub fn describe_breaches(breaches: &[BudgetStatus], currency: &str) -> String {
+    let parts: Vec<String> = breaches
+        .iter()
+        .map(|b| {
+            format!(
+                "{} over budget: {}{:.2} of {}{:.2}",
+                b.label(),
+                currency,
+                b.spent,
+                currency,
+                b.limit
+            )
+        })
+        .collect();
+
+    format!("⚠ {}", parts.join(" · "))
+}
+
+/// Sum debit amounts per tag for transactions dated in `month` ("YYYY-MM")
+pub fn calculate_month_spent_per_tag(transactions: &[Transaction], month: &str) -> HashMap<Tag, f64> {
+    let mut map = HashMap::new();
+    for tx in transactions
+        .iter()
+        .filter(|tx| tx.kind == TransactionType::Debit && tx.date.starts_with(month)) {
+        *map.entry(tx.tag.clone()).or_insert(0.0) += tx.amount;
+    }
+    map
+}
+
+fn days_left_in_month(today: NaiveDate) -> i64 {
+    let (year, month) = if today.month() == 12 {
+        (today.year() + 1, 1)
+    } else {
+        (today.year(), today.month() + 1)
+    };
+
+    let first_of_next = NaiveDate::from_ymd_opt(year, month, 1).unwrap_or(today + Duration::days(1));
+    (first_of_next - today).num_days()
+}
@@ -0,0 +1,718 @@
+use std::{ fs, io::{ self, Write }, path::PathBuf };
+
+use chrono::Local;
+use clap::{ Args, Parser, Subcommand };
+use rusqlite::Connection;
+
+use fitui::{
+    budget::{ self, Budgets },
+    config::load_config,
+    db,
+    entry::{ add_entry, NewEntry },
+    export::{ self, csv::{ CsvExportOptions, COLUMNS }, ExportContext, ExportFormat },
+    filter::{ parse_date_bound, TransactionFilter },
+    hooks::{ self, Event },
+    models::{ Tag, Transaction, TransactionType },
+    query::{ group_transactions, Group, GroupKey, Query },
+    reports::{ self, ReportOutput },
+    stats::StatsSnapshot,
+};
+
+use crate::server;
+
+/// Command line interface. Without a subcommand the TUI starts.
+#[derive(Parser)]
+#[command(name = "fitui", version, about = "Personal finance tracker for the terminal")]
+pub struct Cli {
+    /// Named ledger with its own database and config, e.g. personal or business
+    #[arg(long, global = true, env = "FITUI_PROFILE")]
+    pub profile: Option<String>,
+
+    /// Database file to use instead of the profile's
+    #[arg(long, global = true, env = "FITUI_DB")]
+    pub db: Option<PathBuf>,
+
+    /// config.yaml to use instead of the profile's
+    #[arg(long, global = true, env = "FITUI_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Add a transaction
+    Add(AddArgs),
+
+    /// List transactions
+    List {
+        #[command(flatten)]
+        filter: FilterArgs,
+    },
+
+    /// Delete a transaction by id
+    Delete {
+        id: i32,
+    },
+
+    /// Totals, spending per tag, budgets and goals
+    Stats {
+        #[command(flatten)]
+        filter: FilterArgs,
+    },
+
+    /// Select transactions with a filter expression and print them as a table, JSON or CSV
+    Query(QueryArgs),
+
+    /// Run a report script from the config folder's reports/ directory, or list them
+    Report {
+        /// Script name without .rhai; lists the available reports when omitted
+        name: Option<String>,
+    },
+
+    /// Serve a JSON API for transactions, recurring entries, tags and stats
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:8787")]
+        listen: String,
+
+        /// Require `Authorization: Bearer TOKEN` on every request
+        #[arg(long, env = "FITUI_TOKEN")]
+        token: Option<String>,
+    },
+
+    /// Manage recurring entries
+    Recurring {
+        #[command(subcommand)]
+        command: RecurringCommand,
+    },
+
+    /// Export transactions to CSV, an HTML report or a ledger/hledger/beancount journal
+    Export(ExportArgs),
+}
+
+#[derive(Subcommand)]
+pub enum RecurringCommand {
+    /// List recurring entries
+    List,
+
+    /// Post recurring entries that have fallen due, e.g. from cron or a systemd timer
+    Run {
+        /// Also post occurrences due up to this date: YYYY-MM-DD, or the end of YYYY-MM / YYYY
+        #[arg(long)]
+        until: Option<String>,
+
+        /// Print what would be posted without changing the database
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Args)]
+pub struct AddArgs {
+    amount: f64,
+
+    source: String,
+
+    /// One of the tags from config.yaml
+    #[arg(long, default_value = "other")]
+    tag: String,
+
+    /// YYYY-MM-DD, defaults to today
+    #[arg(long)]
+    date: Option<String>,
+
+    /// Record income instead of an expense
+    #[arg(long)]
+    credit: bool,
+
+    #[arg(long)]
+    notes: Option<String>,
+
+    /// Save an expense over budget even when budget_hard_limit is on
+    #[arg(long)]
+    force: bool,
+}
+
+#[derive(Args)]
+#[command(
+    after_help = "Examples:\n  fitui query 'tag:food amount>100 date:2026-10'\n  fitui query 'type:debit source:amazon*' --format json | jq '.[].amount'\n  fitui query date:2026 --group-by tag,month --format csv"
+)]
+pub struct QueryArgs {
+    /// Filter terms, all of which must match: tag:food,travel  #food  type:debit
+    /// amount>100  amount:10..50  date:2026-10  date>=2026-01-15  source:amazon*
+    /// source:/regex/  notes:refund  plain words. Prefix a term with ! to negate it.
+    expression: Vec<String>,
+
+    /// table, json or csv
+    #[arg(long, default_value = "table", value_parser = ["table", "json", "csv"])]
+    format: String,
+
+    /// Aggregate by tag, month, year, type and/or source, e.g. tag,month
+    #[arg(long, value_delimiter = ',', value_parser = parse_group_key)]
+    group_by: Vec<GroupKey>,
+
+    /// Aggregate everything into a single row of totals
+    #[arg(long, conflicts_with = "group_by")]
+    summary: bool,
+}
+
+#[derive(Args)]
+pub struct ExportArgs {
+    /// csv, html, ledger, hledger or beancount
+    #[arg(long, default_value = "csv", value_parser = parse_format)]
+    format: ExportFormat,
+
+    /// File to write; prints to stdout when omitted
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    #[command(flatten)]
+    filter: FilterArgs,
+
+    /// CSV columns, e.g. date,source,amount (overrides config)
+    #[arg(long, value_delimiter = ',')]
+    columns: Option<Vec<String>>,
+
+    /// CSV date format, e.g. %d/%m/%Y (overrides config)
+    #[arg(long)]
+    date_format: Option<String>,
+
+    /// CSV decimal separator (overrides config)
+    #[arg(long)]
+    decimal_separator: Option<char>,
+
+    /// CSV field delimiter (overrides config)
+    #[arg(long)]
+    delimiter: Option<char>,
+}
+
+/// Options shared by commands that select transactions
+#[derive(Args)]
+pub struct FilterArgs {
+    /// First day to include: YYYY, YYYY-MM or YYYY-MM-DD
+    #[arg(long)]
+    from: Option<String>,
+
+    /// Last day to include: YYYY, YYYY-MM or YYYY-MM-DD
+    #[arg(long)]
+    to: Option<String>,
+
+    /// Only this calendar year (same as --from YEAR --to YEAR)
+    #[arg(long, conflicts_with_all = ["from", "to"])]
+    year: Option<i32>,
+
+    /// Only this month, YYYY-MM
+    #[arg(long, conflicts_with_all = ["from", "to", "year"])]
+    month: Option<String>,
+
+    /// Only these tags (repeatable)
+    #[arg(long = "tag")]
+    tags: Vec<String>,
+
+    /// debit or credit
+    #[arg(long = "type", value_parser = ["debit", "credit"])]
+    kind: Option<String>,
+
+    /// Case-insensitive text in source or notes
+    #[arg(long)]
+    search: Option<String>,
+}
+
+impl FilterArgs {
+    fn to_filter(&self) -> Result<TransactionFilter, String> {
+        let period = self.year.map(|y| y.to_string()).or(self.month.clone());
+        let from = self.from.as_deref().or(period.as_deref());
+        let to = self.to.as_deref().or(period.as_deref());
+
+        Ok(TransactionFilter {
+            from: from.map(|d| parse_date_bound(d, false)).transpose()?,
+            to: to.map(|d| parse_date_bound(d, true)).transpose()?,
+            tags: self.tags
+                .iter()
+                .map(|t| Tag::new(t))
+                .collect(),
+            kind: self.kind.as_deref().map(TransactionType::parse_lossy),
+            search: self.search.clone(),
+        })
+    }
+}
+
+fn parse_format(name: &str) -> Result<ExportFormat, String> {
+    ExportFormat::from_name(name).ok_or_else(|| {
+        let names: Vec<&str> = ExportFormat::ALL
+            .iter()
+            .map(|f| f.name())
+            .collect();
+        format!("expected one of {}", names.join(", "))
+    })
+}
+
+fn parse_group_key(name: &str) -> Result<GroupKey, String> {
+    GroupKey::from_name(name).ok_or_else(|| {
+        let names: Vec<&str> = GroupKey::ALL
+            .iter()
+            .map(|k| k.name())
+            .collect();
+        format!("expected one of {}", names.join(", "))
+    })
+}
+
+pub fn run(command: Command, conn: &Connection) -> Result<(), String> {
+    match command {
+        Command::Add(args) => run_add(args, conn),
+        Command::List { filter } => run_list(&filter, conn),
+        Command::Delete { id } => run_delete(id, conn),
+        Command::Stats { filter } => run_stats(&filter, conn),
+        Command::Query(args) => run_query(args, conn),
+        Command::Recurring { command: RecurringCommand::List } => run_recurring_list(conn),
+        Command::Recurring { command: RecurringCommand::Run { until, dry_run } } =>
+            run_recurring(until.as_deref(), dry_run, conn),
+        Command::Report { name } => run_report(name.as_deref(), conn),
+        Command::Serve { listen, token } => server::serve(conn, &listen, token.as_deref()),
+        Command::Export(args) => run_export(args, conn),
+    }
+}
+
+/// Same checks as saving from the TUI form: tag validation, optional rules,
+/// budget alerts and a duplicate warning
+fn run_add(args: AddArgs, conn: &Connection) -> Result<(), String> {
+    let config = load_config();
+    let entry = NewEntry {
+        source: args.source,
+        amount: args.amount,
+        kind: if args.credit { TransactionType::Credit } else { TransactionType::Debit },
+        tag: args.tag,
+        date: args.date,
+        notes: args.notes.unwrap_or_default(),
+        force: args.force,
+    };
+
+    let added = add_entry(conn, &config, entry)?;
+    let tx = &added.transaction;
+
+    for warning in &added.warnings {
+        eprintln!("warning: {}", warning);
+    }
+    println!(
+        "Added #{}: {} {} {}{:.2} #{} on {}",
+        tx.id,
+        tx.kind.as_str(),
+        tx.source,
+        config.currency,
+        tx.amount,
+        tx.tag.as_str(),
+        tx.date
+    );
+
+    if !added.breaches.is_empty() {
+        eprintln!("{}", budget::describe_breaches(&added.breaches, &config.currency));
+    }
+    if let Some(dup) = &added.duplicate {
+        eprintln!("warning: looks like a duplicate of #{} {} on {}", dup.id, dup.source, dup.date);
+    }
+
+    Ok(())
+}
+
+fn run_list(filter: &FilterArgs, conn: &Connection) -> Result<(), String> {
+    let currency = load_config().currency;
+    let mut transactions = filter.to_filter()?.apply(&db::get_transactions(conn).map_err(|e| e.to_string())?);
+    transactions.sort_by(|a, b| a.date.cmp(&b.date).then(a.id.cmp(&b.id)));
+
+    print_transactions(&transactions, &currency);
+    Ok(())
+}
+
+fn print_transactions(transactions: &[Transaction], currency: &str) {
+    let rows: Vec<Vec<String>> = transactions
+        .iter()
+        .map(|tx| {
+            let sign = if tx.kind == TransactionType::Credit { "+" } else { "-" };
+            vec![
+                tx.id.to_string(),
+                tx.date.clone(),
+                tx.source.clone(),
+                format!("{}{}{:.2}", sign, currency, tx.amount),
+                format!("#{}", tx.tag.as_str()),
+                tx.notes.clone()
+            ]
+        })
+        .collect();
+
+    print_table(&["ID", "DATE", "SOURCE", "AMOUNT", "TAG", "NOTES"], &rows, &[3]);
+}
+
+fn run_query(args: QueryArgs, conn: &Connection) -> Result<(), String> {
+    let currency = load_config().currency;
+    let query = Query::parse(&args.expression.join(" "))?;
+
+    let mut transactions = query.apply(&db::get_transactions(conn).map_err(|e| e.to_string())?);
+    transactions.sort_by(|a, b| a.date.cmp(&b.date).then(a.id.cmp(&b.id)));
+
+    if args.group_by.is_empty() && !args.summary {
+        match args.format.as_str() {
+            "json" => {
+                let json = serde_json::to_string_pretty(&transactions).map_err(|e| e.to_string())?;
+                println!("{}", json);
+            }
+            "csv" => {
+                let options = CsvExportOptions {
+                    columns: COLUMNS.iter()
+                        .map(|c| c.to_string())
+                        .collect(),
+                    ..CsvExportOptions::default()
+                };
+                print!("{}", export::csv::write_csv(&transactions, &options)?);
+            }
+            _ => print_transactions(&transactions, &currency),
+        }
+        return Ok(());
+    }
+
+    let groups = group_transactions(&transactions, &args.group_by);
+    let mut headers: Vec<&str> = args.group_by
+        .iter()
+        .map(|k| k.name())
+        .collect();
+    headers.extend(["count", "earned", "spent", "net"]);
+
+    match args.format.as_str() {
+        "json" => {
+            let rows: Vec<serde_json::Value> = groups
+                .iter()
+                .map(|g| group_json(g, &args.group_by))
+                .collect();
+            let json = serde_json::to_string_pretty(&rows).map_err(|e| e.to_string())?;
+            println!("{}", json);
+        }
+        "csv" => {
+            let mut writer = ::csv::Writer::from_writer(io::stdout());
+            writer.write_record(&headers).map_err(|e| e.to_string())?;
+            for group in &groups {
+                writer.write_record(group_cells(group, "")).map_err(|e| e.to_string())?;
+            }
+            writer.flush().map_err(|e| e.to_string())?;
+        }
+        _ => {
+            let headers: Vec<String> = headers
+                .iter()
+                .map(|h| h.to_uppercase())
+                .collect();
+            let headers: Vec<&str> = headers
+                .iter()
+                .map(|h| h.as_str())
+                .collect();
+            let rows: Vec<Vec<String>> = groups
+                .iter()
+                .map(|g| group_cells(g, &currency))
+                .collect();
+            let keys = args.group_by.len();
+            print_table(&headers, &rows, &[keys, keys + 1, keys + 2, keys + 3]);
+        }
+    }
+
+    Ok(())
+}
+
+fn group_cells(group: &Group, currency: &str) -> Vec<String> {
+    let mut cells = group.keys.clone();
+    cells.push(group.count.to_string());
+    for amount in [group.earned, group.spent, group.net] {
+        cells.push(format!("{}{:.2}", currency, amount));
+    }
+    cells
+}
+
+fn group_json(group: &Group, keys: &[GroupKey]) -> serde_json::Value {
+    // Round to cents so sums like 0.1 + 0.2 don't leak float noise into scripts
+    let cents = |amount: f64| (amount * 100.0).round() / 100.0;
+
+    let mut object = serde_json::Map::new();
+    for (key, value) in keys.iter().zip(&group.keys) {
+        object.insert(key.name().to_string(), value.clone().into());
+    }
+    object.insert("count".into(), group.count.into());
+    object.insert("earned".into(), cents(group.earned).into());
+    object.insert("spent".into(), cents(group.spent).into());
+    object.insert("net".into(), cents(group.net).into());
+
+    serde_json::Value::Object(object)
+}
+
+fn run_delete(id: i32, conn: &Connection) -> Result<(), String> {
+    let transactions = db::get_transactions(conn).map_err(|e| e.to_string())?;
+    let tx = transactions
+        .iter()
+        .find(|tx| tx.id == id)
+        .ok_or_else(|| format!("no transaction with id {}", id))?;
+
+    db::delete_transaction(conn, id).map_err(|e| e.to_string())?;
+    println!("Deleted #{}: {} {:.2} on {}", tx.id, tx.source, tx.amount, tx.date);
+
+    for error in hooks::fire(&load_config().hooks, &Event::TransactionDeleted(tx)) {
+        eprintln!("warning: {}", error);
+    }
+    Ok(())
+}
+
+fn run_stats(filter: &FilterArgs, conn: &Connection) -> Result<(), String> {
+    let config = load_config();
+    let currency = &config.currency;
+    let transactions = filter.to_filter()?.apply(&db::get_transactions(conn).map_err(|e| e.to_string())?);
+    let goals = db::get_goals(conn).map_err(|e| e.to_string())?;
+    let snapshot = StatsSnapshot::new(&transactions, &Budgets::from_config(&config), &goals);
+
+    println!("Transactions  {}", snapshot.tx_count);
+    println!("Earned        {}{:.2}", currency, snapshot.earned);
+    println!("Spent         {}{:.2}", currency, snapshot.spent);
+    println!("Balance       {}{:.2}", currency, snapshot.balance);
+
+    let mut per_tag: Vec<(&Tag, &f64)> = snapshot.per_tag.iter().collect();
+    per_tag.sort_by(|a, b| b.1.partial_cmp(a.1).unwrap_or(std::cmp::Ordering::Equal));
+    if !per_tag.is_empty() {
+        println!();
+        let rows: Vec<Vec<String>> = per_tag
+            .iter()
+            .map(|(tag, amount)| {
+                let share = if snapshot.spent > 0.0 { (*amount / snapshot.spent) * 100.0 } else { 0.0 };
+                vec![format!("#{}", tag.as_str()), format!("{}{:.2}", currency, amount), format!("{:.1}%", share)]
+            })
+            .collect();
+        print_table(&["TAG", "SPENT", "SHARE"], &rows, &[1, 2]);
+    }
+
+    let report = &snapshot.budget;
+    if !report.is_empty() {
+        println!("\nBudgets for {}", report.month);
+        let rows: Vec<Vec<String>> = report.overall
+            .iter()
+            .chain(report.tags.iter())
+            .map(|s| {
+                vec![
+                    s.label(),
+                    format!("{}{:.2}", currency, s.spent),
+                    format!("{}{:.2}", currency, s.limit),
+                    format!("{:.0}%", s.ratio() * 100.0)
+                ]
+            })
+            .collect();
+        print_table(&["BUDGET", "SPENT", "LIMIT", "USED"], &rows, &[1, 2, 3]);
+    }
+
+    if !snapshot.goals.is_empty() {
+        println!("\nGoals");
+        let rows: Vec<Vec<String>> = snapshot.goals
+            .iter()
+            .map(|g| {
+                vec![
+                    g.goal.name.clone(),
+                    format!("{}{:.2}", currency, g.saved),
+                    format!("{}{:.2}", currency, g.goal.target),
+                    g.goal.target_date.clone(),
+                    format!("{}{:.2}/month", currency, g.required_monthly)
+                ]
+            })
+            .collect();
+        print_table(&["GOAL", "SAVED", "TARGET", "BY", "NEEDED"], &rows, &[1, 2]);
+    }
+
+    Ok(())
+}
+
+fn run_recurring_list(conn: &Connection) -> Result<(), String> {
+    let currency = load_config().currency;
+    let entries = db::get_recurring_entries(conn).map_err(|e| e.to_string())?;
+
+    let rows: Vec<Vec<String>> = entries
+        .iter()
+        .map(|e| {
+            vec![
+                e.id.to_string(),
+                e.day.to_string(),
+                e.source.clone(),
+                format!("{}{:.2}", currency, e.amount),
+                e.kind.as_str().to_string(),
+                format!("#{}", e.tag.as_str()),
+                e.last_inserted_month.clone(),
+                (if e.active { "active" } else { "paused" }).to_string()
+            ]
+        })
+        .collect();
+
+    print_table(&["ID", "DAY", "SOURCE", "AMOUNT", "TYPE", "TAG", "LAST POSTED", "STATUS"], &rows, &[3]);
+    Ok(())
+}
+
+fn run_report(name: Option<&str>, conn: &Connection) -> Result<(), String> {
+    let scripts = reports::list_scripts();
+
+    let Some(name) = name else {
+        if scripts.is_empty() {
+            println!("No reports in {}", reports::reports_dir().display());
+        }
+        for script in &scripts {
+            println!("{}", reports::script_name(script));
+        }
+        return Ok(());
+    };
+
+    let path = scripts
+        .iter()
+        .find(|p| reports::script_name(p) == name)
+        .ok_or_else(|| format!("no report '{}' in {}", name, reports::reports_dir().display()))?;
+
+    let config = load_config();
+    let transactions = db::get_transactions(conn).map_err(|e| e.to_string())?;
+    let goals = db::get_goals(conn).map_err(|e| e.to_string())?;
+    let snapshot = StatsSnapshot::new(&transactions, &Budgets::from_config(&config), &goals);
+
+    match reports::run_report(path, &transactions, &snapshot, &config.currency).output {
+        ReportOutput::Text(text) => println!("{}", text),
+        ReportOutput::Table { text, headers, rows } => {
+            if !text.is_empty() {
+                println!("{}\n", text);
+            }
+            let headers: Vec<&str> = headers
+                .iter()
+                .map(|h| h.as_str())
+                .collect();
+            print_table(&headers, &rows, &[]);
+        }
+        ReportOutput::Error(e) => {
+            return Err(format!("report '{}' failed: {}", name, e));
+        }
+    }
+
+    Ok(())
+}
+
+fn run_recurring(until: Option<&str>, dry_run: bool, conn: &Connection) -> Result<(), String> {
+    let config = load_config();
+    let currency = &config.currency;
+    let today = Local::now().date_naive();
+    let until = match until {
+        Some(date) => parse_date_bound(date, true)?,
+        None => today,
+    };
+
+    let occurrences: Vec<(String, String, f64, TransactionType, Tag)> = if dry_run {
+        db::pending_recurring(conn, today, until)
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .map(|(e, due)| (due.format("%Y-%m-%d").to_string(), e.source, e.amount, e.kind, e.tag))
+            .collect()
+    } else {
+        let posted = db::insert_recurring_until(conn, today, until).map_err(|e| e.to_string())?;
+        if !posted.is_empty() {
+            for error in hooks::fire(&config.hooks, &Event::RecurringPosted(&posted)) {
+                eprintln!("warning: {}", error);
+            }
+        }
+
+        posted
+            .into_iter()
+            .map(|tx| (tx.date, tx.source, tx.amount, tx.kind, tx.tag))
+            .collect()
+    };
+
+    if occurrences.is_empty() {
+        println!("Nothing due until {}", until.format("%Y-%m-%d"));
+        return Ok(());
+    }
+
+    let verb = if dry_run { "Would post" } else { "Posted" };
+    println!("{} {} recurring transaction{}:", verb, occurrences.len(), if occurrences.len() == 1 { "" } else { "s" });
+
+    let rows: Vec<Vec<String>> = occurrences
+        .into_iter()
+        .map(|(date, source, amount, kind, tag)| {
+            let sign = if kind == TransactionType::Credit { "+" } else { "-" };
+            vec![date, source, format!("{}{}{:.2}", sign, currency, amount), format!("#{}", tag.as_str())]
+        })
+        .collect();
+
+    print_table(&["DATE", "SOURCE", "AMOUNT", "TAG"], &rows, &[2]);
+    Ok(())
+}
+
+/// Plain aligned columns; `right` lists the columns to right-align
+fn print_table(headers: &[&str], rows: &[Vec<String>], right: &[usize]) {
+    let mut widths: Vec<usize> = headers
+        .iter()
+        .map(|h| h.chars().count())
+        .collect();
+    for row in rows {
+        // Rows from report scripts can be wider than their header
+        if row.len() > widths.len() {
+            widths.resize(row.len(), 0);
+        }
+        for (i, cell) in row.iter().enumerate() {
+            widths[i] = widths[i].max(cell.chars().count());
+        }
+    }
+
+    let line = |cells: Vec<&str>| {
+        let padded: Vec<String> = cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| {
+                if right.contains(&i) {
+                    format!("{:>width$}", cell, width = widths[i])
+                } else {
+                    format!("{:<width$}", cell, width = widths[i])
+                }
+            })
+            .collect();
+        println!("{}", padded.join("  ").trim_end());
+    };
+
+    line(headers.to_vec());
+    for row in rows {
+        line(row.iter().map(|c| c.as_str()).collect());
+    }
+}
+
+fn run_export(args: ExportArgs, conn: &Connection) -> Result<(), String> {
+    let config = load_config();
+
+    let mut options = config.csv_export.clone();
+    if let Some(columns) = args.columns {
+        options.columns = columns;
+    }
+    if let Some(format) = args.date_format {
+        options.date_format = format;
+    }
+    if let Some(separator) = args.decimal_separator {
+        options.decimal_separator = separator;
+    }
+    if let Some(delimiter) = args.delimiter {
+        options.delimiter = delimiter;
+    }
+
+    let transactions = db::get_transactions(conn).map_err(|e| e.to_string())?;
+    let recurring = db::get_recurring_entries(conn).map_err(|e| e.to_string())?;
+    let selected = args.filter.to_filter()?.apply(&transactions);
+
+    let budgets = Budgets::from_config(&config);
+    let context = ExportContext {
+        recurring: &recurring,
+        currency: &config.currency,
+        csv: &options,
+        budgets: &budgets,
+    };
+    let content = export::render(args.format, &selected, &context)?;
+
+    match args.output {
+        Some(path) => {
+            fs::write(&path, content).map_err(|e| format!("Could not write {}: {}", path.display(), e))?;
+            eprintln!("Exported {} transactions to {}", selected.len(), path.display());
+        }
+        None => {
+            io::stdout().write_all(content.as_bytes()).map_err(|e| e.to_string())?;
+        }
+    }
+
+    Ok(())
+}
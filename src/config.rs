@@ -1,12 +1,61 @@
 use serde::{Deserialize, Serialize};
-use directories::ProjectDirs;
-use std::{fs, path::PathBuf};
+
+use crate::{
+    duplicates::DuplicateTolerance, export::csv::CsvExportOptions, hooks::Hooks, import::csv::CsvProfile,
+    profile,
+    rules::Rule,
+};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Config {
     pub tags: Vec<String>,
     #[serde(default = "default_currency")]
     pub currency: String,
+
+    // Monthly spending limit per tag, e.g. `food: 8000`
+    #[serde(default)]
+    pub budgets: BTreeMap<String, f64>,
+
+    // Optional limit on total spending per month
+    #[serde(default)]
+    pub monthly_budget: Option<f64>,
+
+    // When true, entries that break a budget need confirmation before saving
+    #[serde(default)]
+    pub budget_hard_limit: bool,
+
+    // Named CSV column mappings used by the importer
+    #[serde(default)]
+    pub import_profiles: BTreeMap<String, CsvProfile>,
+
+    // OFX/QFX account id -> tag given to that account's transactions
+    #[serde(default)]
+    pub ofx_accounts: BTreeMap<String, String>,
+
+    // Ordered categorisation rules applied to imported rows
+    #[serde(default)]
+    pub rules: Vec<Rule>,
+
+    // Also apply rules to transactions added by hand
+    #[serde(default)]
+    pub apply_rules_to_manual: bool,
+
+    // How close two transactions must be to be flagged as duplicates
+    #[serde(default)]
+    pub duplicates: DuplicateTolerance,
+
+    // Columns, date format and separators for CSV exports
+    #[serde(default)]
+    pub csv_export: CsvExportOptions,
+
+    // Shell commands run on data events, receiving the event as JSON on stdin
+    #[serde(default)]
+    pub hooks: Hooks,
 }
 
 fn default_currency() -> String {
@@ -25,22 +74,33 @@ impl Default for Config {
                 "other".into(),
             ],
             currency: default_currency(),
+            budgets: BTreeMap::new(),
+            monthly_budget: None,
+            budget_hard_limit: false,
+            import_profiles: BTreeMap::from([("generic".into(), CsvProfile::generic())]),
+            ofx_accounts: BTreeMap::new(),
+            rules: Vec::new(),
+            apply_rules_to_manual: false,
+            duplicates: DuplicateTolerance::default(),
+            csv_export: CsvExportOptions::default(),
+            hooks: Hooks::default(),
         }
     }
 }
 
-fn config_path() -> PathBuf {
-    let proj_dirs =
-        ProjectDirs::from("com", "ayan", "fitui").expect("Could not find config directory");
-
-    let config_dir = proj_dirs.config_dir();
-    fs::create_dir_all(config_dir).expect("Failed to create config directory");
+fn config_path() -> Result<PathBuf, String> {
+    let path = profile::active().config;
+    profile::ensure_parent(&path)?;
+    Ok(path)
+}
 
-    config_dir.join("config.yaml")
+pub fn save_config(config: &Config) -> Result<(), String> {
+    let yaml = serde_yaml::to_string(config).map_err(|e| e.to_string())?;
+    fs::write(config_path()?, yaml).map_err(|e| e.to_string())
 }
 
 pub fn load_config() -> Config {
-    let path = config_path();
+    let path = config_path().expect("Failed to create config directory");
 
     // Auto-create default config if missing
     if !path.exists() {
@@ -51,7 +111,7 @@ pub fn load_config() -> Config {
 
         fs::write(&path, yaml).expect("Failed to write default config.yaml");
 
-        println!("Created default config at: {:?}", path);
+        eprintln!("Created default config at: {:?}", path);
 
         return default;
     }
@@ -59,3 +119,9 @@ pub fn load_config() -> Config {
     let text = fs::read_to_string(&path).expect("Failed to read config.yaml");
     serde_yaml::from_str(&text).expect("Invalid YAML format")
 }
+
+/// Read a config.yaml without creating it or touching the active profile
+pub fn read_config(path: &Path) -> Result<Config, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+    serde_yaml::from_str(&text).map_err(|e| format!("Invalid config {}: {}", path.display(), e))
+}
@@ -1,25 +1,26 @@
 use rusqlite::{Connection, Result};
-use std::collections::HashMap;
-use std::fs;
-
-use directories::ProjectDirs;
-
-use crate::models::{RecurringEntry, Tag, Transaction, TransactionType};
-
-pub fn init_db() -> Result<Connection> {
-    // Store DB in the OS-standard application data directory
-    let proj_dirs =
-        ProjectDirs::from("com", "ayan", "fitui").expect("Could not determine data directory");
-
-    let data_dir = proj_dirs.data_dir();
-    fs::create_dir_all(data_dir).expect("Failed to create data directory");
-
-    let db_path = data_dir.join("budget.db");
+use std::{collections::HashMap, path::Path};
+
+use chrono::{Datelike, NaiveDate};
+use crate::models::{
+    Envelope, EnvelopeAssignment, Goal, RecurringEntry, Tag, Transaction, TransactionType,
+};
+use crate::profile;
+
+pub fn init_db() -> std::result::Result<Connection, String> {
+    // The active profile's database, by default in the OS-standard application data directory
+    let db_path = profile::active().db;
+    profile::ensure_parent(&db_path)?;
     
     #[cfg(debug_assertions)]
-    println!("Database location: {:?}", db_path);
+    eprintln!("Database location: {:?}", db_path);
+
+    open_db(&db_path).map_err(|e| format!("Could not open database {}: {}", db_path.display(), e))
+}
 
-    let conn = Connection::open(db_path)?;
+/// Open the database at `path`, creating or upgrading the schema as needed
+pub fn open_db(path: &Path) -> Result<Connection> {
+    let conn = Connection::open(path)?;
 
     // Create schema on first run if it doesn't exist yet
     conn.execute(
@@ -43,17 +44,108 @@ pub fn init_db() -> Result<Connection> {
             kind TEXT NOT NULL,
             tag TEXT NOT NULL,
             last_inserted_month TEXT NOT NULL,
-            active INTEGER NOT NULL DEFAULT 1
+            active INTEGER NOT NULL DEFAULT 1,
+            day INTEGER NOT NULL DEFAULT 1
+        )",
+        [],
+    )?;
+
+    // Envelopes group spending by tag; assignments are made per month
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS envelopes (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            tag TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS envelope_assignments (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            envelope_id INTEGER NOT NULL REFERENCES envelopes(id) ON DELETE CASCADE,
+            month TEXT NOT NULL,
+            amount REAL NOT NULL,
+            UNIQUE(envelope_id, month)
+        )",
+        [],
+    )?;
+
+    // Statement transaction ids (OFX FITID) already imported, per account
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS imported_ids (
+            account TEXT NOT NULL,
+            external_id TEXT NOT NULL,
+            transaction_id INTEGER NOT NULL,
+            PRIMARY KEY (account, external_id)
+        )",
+        [],
+    )?;
+
+    // Savings goals
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS goals (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            target REAL NOT NULL,
+            target_date TEXT NOT NULL,
+            tag TEXT NOT NULL
         )",
         [],
     )?;
 
+    // Log of budgets broken at entry time
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS budget_events (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            created_at TEXT NOT NULL,
+            month TEXT NOT NULL,
+            tag TEXT,
+            budget_limit REAL NOT NULL,
+            spent REAL NOT NULL,
+            source TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    // Pairs the user reviewed and chose to keep as separate transactions
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS duplicate_dismissals (
+            first_id INTEGER NOT NULL,
+            second_id INTEGER NOT NULL,
+            PRIMARY KEY (first_id, second_id)
+        )",
+        [],
+    )?;
+
+    // Databases created before the day column existed need it added
+    ensure_column(&conn, "recurring_entries", "day", "INTEGER NOT NULL DEFAULT 1")?;
+    ensure_column(&conn, "transactions", "notes", "TEXT NOT NULL DEFAULT ''")?;
+
     Ok(conn)
 }
 
+fn ensure_column(conn: &Connection, table: &str, column: &str, definition: &str) -> Result<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let exists = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<Result<Vec<_>>>()?
+        .iter()
+        .any(|name| name == column);
+
+    if !exists {
+        conn.execute(
+            &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+            [],
+        )?;
+    }
+
+    Ok(())
+}
+
 pub fn get_transactions(conn: &Connection) -> Result<Vec<Transaction>> {
     let mut stmt = conn.prepare(
-        "SELECT id, source, amount, kind, tag, date
+        "SELECT id, source, amount, kind, tag, date, notes
          FROM transactions
          ORDER BY date DESC",
     )?;
@@ -65,12 +157,13 @@ pub fn get_transactions(conn: &Connection) -> Result<Vec<Transaction>> {
             amount: row.get(2)?,
 
             // Stored as string in DB, converted back into enum
-            kind: TransactionType::from_str(&row.get::<_, String>(3)?),
+            kind: TransactionType::parse_lossy(&row.get::<_, String>(3)?),
 
             // Tags are wrapped in your custom Tag type
-            tag: Tag::from_str(&row.get::<_, String>(4)?),
+            tag: Tag::new(&row.get::<_, String>(4)?),
 
             date: row.get(5)?,
+            notes: row.get(6)?,
         })
     })?;
 
@@ -121,6 +214,69 @@ pub fn update_transaction(
     Ok(())
 }
 
+pub fn set_transaction_notes(conn: &Connection, id: i64, notes: &str) -> Result<()> {
+    conn.execute("UPDATE transactions SET notes = ?1 WHERE id = ?2", (notes, id))?;
+    Ok(())
+}
+
+// Used by rules to rewrite categorisation without touching amount or date
+pub fn recategorize_transaction(
+    conn: &Connection,
+    id: i32,
+    source: &str,
+    tag: &Tag,
+    notes: &str,
+) -> Result<()> {
+    conn.execute(
+        "UPDATE transactions SET source = ?1, tag = ?2, notes = ?3 WHERE id = ?4",
+        (source, tag.as_str(), notes, id),
+    )?;
+
+    Ok(())
+}
+
+/// Fold `remove` into `keep`: notes are combined, statement ids move over so
+/// re-imports still recognise the row, then `remove` is deleted. Returns `keep`
+/// with its combined notes.
+pub fn merge_transactions(conn: &Connection, keep: &Transaction, remove: &Transaction) -> Result<Transaction> {
+    let tx = conn.unchecked_transaction()?;
+
+    let notes = match (keep.notes.is_empty(), remove.notes.is_empty()) {
+        (_, true) => keep.notes.clone(),
+        (true, false) => remove.notes.clone(),
+        (false, false) if keep.notes == remove.notes => keep.notes.clone(),
+        (false, false) => format!("{}; {}", keep.notes, remove.notes),
+    };
+
+    tx.execute(
+        "UPDATE transactions SET notes = ?1 WHERE id = ?2",
+        (&notes, keep.id),
+    )?;
+    tx.execute(
+        "UPDATE imported_ids SET transaction_id = ?1 WHERE transaction_id = ?2",
+        [keep.id, remove.id],
+    )?;
+    tx.execute("DELETE FROM transactions WHERE id = ?1", [remove.id])?;
+
+    tx.commit()?;
+    Ok(Transaction { notes, ..keep.clone() })
+}
+
+pub fn get_duplicate_dismissals(conn: &Connection) -> Result<Vec<(i32, i32)>> {
+    let mut stmt = conn.prepare("SELECT first_id, second_id FROM duplicate_dismissals")?;
+    let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+
+    rows.collect()
+}
+
+pub fn dismiss_duplicate(conn: &Connection, first_id: i32, second_id: i32) -> Result<()> {
+    conn.execute(
+        "INSERT OR IGNORE INTO duplicate_dismissals (first_id, second_id) VALUES (?1, ?2)",
+        [first_id, second_id],
+    )?;
+    Ok(())
+}
+
 pub fn total_earned(conn: &Connection) -> Result<f64> {
     conn.query_row(
         "SELECT COALESCE(SUM(amount), 0)
@@ -154,7 +310,7 @@ pub fn spent_per_tag(conn: &Connection) -> Result<HashMap<Tag, f64>> {
         let tag_str: String = row.get(0)?;
         let total: f64 = row.get(1)?;
 
-        Ok((Tag::from_str(&tag_str), total))
+        Ok((Tag::new(&tag_str), total))
     })?;
 
     let mut map = HashMap::new();
@@ -168,7 +324,7 @@ pub fn spent_per_tag(conn: &Connection) -> Result<HashMap<Tag, f64>> {
 // Recurring entry functions
 pub fn get_recurring_entries(conn: &Connection) -> Result<Vec<RecurringEntry>> {
     let mut stmt = conn.prepare(
-        "SELECT id, source, amount, kind, tag, last_inserted_month, active
+        "SELECT id, source, amount, kind, tag, last_inserted_month, active, day
          FROM recurring_entries
          ORDER BY id DESC",
     )?;
@@ -178,10 +334,11 @@ pub fn get_recurring_entries(conn: &Connection) -> Result<Vec<RecurringEntry>> {
             id: row.get(0)?,
             source: row.get(1)?,
             amount: row.get(2)?,
-            kind: TransactionType::from_str(&row.get::<_, String>(3)?),
-            tag: Tag::from_str(&row.get::<_, String>(4)?),
+            kind: TransactionType::parse_lossy(&row.get::<_, String>(3)?),
+            tag: Tag::new(&row.get::<_, String>(4)?),
             last_inserted_month: row.get(5)?,
             active: row.get::<_, i32>(6)? != 0,
+            day: row.get(7)?,
         })
     })?;
 
@@ -199,17 +356,20 @@ pub fn add_recurring_entry(
     amount: f64,
     kind: TransactionType,
     tag: &Tag,
+    day: u32,
+    last_inserted_month: &str, // Empty string indicates it hasn't been inserted yet
 ) -> Result<()> {
     conn.execute(
-        "INSERT INTO recurring_entries (source, amount, kind, tag, last_inserted_month, active)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        "INSERT INTO recurring_entries (source, amount, kind, tag, last_inserted_month, active, day)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
         (
             source,
             amount,
             kind.as_str(),
             tag.as_str(),
-            "", // Empty string indicates it hasn't been inserted yet
+            last_inserted_month,
             1,
+            day,
         ),
     )?;
 
@@ -229,40 +389,267 @@ pub fn toggle_recurring_entry(conn: &Connection, id: i32, active: bool) -> Resul
     Ok(())
 }
 
-// Auto-insert recurring entries for the current month
-pub fn insert_recurring_for_month(conn: &Connection, current_month: &str) -> Result<()> {
-    // Get all active recurring entries that haven't been inserted this month
-    let mut stmt = conn.prepare(
-        "SELECT id, source, amount, kind, tag FROM recurring_entries
-         WHERE active = 1 AND last_inserted_month != ?1",
-    )?;
+/// Changes whenever another connection (the CLI, the API server, a second
+/// TUI) commits to the database; our own writes leave it unchanged
+pub fn data_version(conn: &Connection) -> Result<i64> {
+    conn.query_row("PRAGMA data_version", [], |row| row.get(0))
+}
+
+// Auto-insert recurring entries that have fallen due this month
+pub fn insert_recurring_for_month(conn: &Connection, today: NaiveDate) -> Result<Vec<Transaction>> {
+    insert_recurring_until(conn, today, today)
+}
 
-    let entries: Vec<_> = stmt
-        .query_map([current_month], |row| {
-            Ok((
-                row.get::<_, i32>(0)?,
-                row.get::<_, String>(1)?,
-                row.get::<_, f64>(2)?,
-                row.get::<_, String>(3)?,
-                row.get::<_, String>(4)?,
-            ))
-        })?
-        .collect::<Result<Vec<_>, _>>()?;
-
-    // Insert each recurring entry as a transaction for this month
-    for (rec_id, source, amount, kind, tag) in entries {
-        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
-        let kind_enum = TransactionType::from_str(&kind);
-        let tag_obj = Tag::from_str(&tag);
-
-        add_transaction(conn, &source, amount, kind_enum, &tag_obj, &today)?;
+/// Occurrences of active recurring entries that fall due from the start of
+/// `today`'s month up to `until`, skipping months an entry was already posted
+/// for. Sorted by due date.
+pub fn pending_recurring(
+    conn: &Connection,
+    today: NaiveDate,
+    until: NaiveDate,
+) -> Result<Vec<(RecurringEntry, NaiveDate)>> {
+    let entries: Vec<RecurringEntry> = get_recurring_entries(conn)?
+        .into_iter()
+        .filter(|e| e.active)
+        .collect();
+
+    let mut pending = Vec::new();
+    let (mut year, mut month) = (today.year(), today.month());
+
+    while (year, month) <= (until.year(), until.month()) {
+        let month_key = format!("{:04}-{:02}", year, month);
+
+        for entry in &entries {
+            let due = entry.due_date(year, month);
+
+            // Already posted, or not due yet; a later run will pick it up
+            if entry.last_inserted_month.as_str() >= month_key.as_str() || due > until {
+                continue;
+            }
+
+            pending.push((entry.clone(), due));
+        }
+
+        if month == 12 {
+            year += 1;
+            month = 1;
+        } else {
+            month += 1;
+        }
+    }
+
+    pending.sort_by_key(|(entry, due)| (*due, entry.id));
+    Ok(pending)
+}
+
+/// Post every pending occurrence up to `until` and mark each entry's month
+pub fn insert_recurring_until(
+    conn: &Connection,
+    today: NaiveDate,
+    until: NaiveDate,
+) -> Result<Vec<Transaction>> {
+    let mut posted = Vec::new();
+
+    for (entry, due) in pending_recurring(conn, today, until)? {
+        let date = due.format("%Y-%m-%d").to_string();
+        add_transaction(conn, &entry.source, entry.amount, entry.kind, &entry.tag, &date)?;
+
+        posted.push(Transaction {
+            id: conn.last_insert_rowid() as i32,
+            source: entry.source,
+            amount: entry.amount,
+            kind: entry.kind,
+            tag: entry.tag,
+            date,
+            notes: String::new(),
+        });
 
         // Update the last_inserted_month
         conn.execute(
             "UPDATE recurring_entries SET last_inserted_month = ?1 WHERE id = ?2",
-            (current_month, rec_id),
+            (due.format("%Y-%m").to_string(), entry.id),
         )?;
     }
 
+    Ok(posted)
+}
+
+pub fn is_imported(conn: &Connection, account: &str, external_id: &str) -> Result<bool> {
+    conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM imported_ids WHERE account = ?1 AND external_id = ?2)",
+        (account, external_id),
+        |row| row.get(0),
+    )
+}
+
+pub fn mark_imported(
+    conn: &Connection,
+    account: &str,
+    external_id: &str,
+    transaction_id: i64,
+) -> Result<()> {
+    conn.execute(
+        "INSERT OR IGNORE INTO imported_ids (account, external_id, transaction_id)
+         VALUES (?1, ?2, ?3)",
+        (account, external_id, transaction_id),
+    )?;
+
+    Ok(())
+}
+
+// Every (account, external id, transaction id) recorded by imports, for backups
+pub fn get_imported_ids(conn: &Connection) -> Result<Vec<(String, String, i64)>> {
+    let mut stmt = conn.prepare("SELECT account, external_id, transaction_id FROM imported_ids")?;
+    let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+
+    rows.collect()
+}
+
+// Used before restoring a backup over the current data
+pub fn clear_all_data(conn: &Connection) -> Result<()> {
+    for table in [
+        "transactions",
+        "recurring_entries",
+        "envelope_assignments",
+        "envelopes",
+        "goals",
+        "imported_ids",
+        "duplicate_dismissals",
+    ] {
+        conn.execute(&format!("DELETE FROM {}", table), [])?;
+    }
+
+    Ok(())
+}
+
+// Record that saving `source` pushed a budget over its limit; `tag` is None
+// for the overall monthly budget
+pub fn add_budget_event(
+    conn: &Connection,
+    month: &str,
+    tag: Option<&Tag>,
+    limit: f64,
+    spent: f64,
+    source: &str,
+) -> Result<()> {
+    let created_at = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+    conn.execute(
+        "INSERT INTO budget_events (created_at, month, tag, budget_limit, spent, source)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        (created_at, month, tag.map(|t| t.as_str()), limit, spent, source),
+    )?;
+
+    Ok(())
+}
+
+// Envelope functions
+pub fn get_envelopes(conn: &Connection) -> Result<Vec<Envelope>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, name, tag
+         FROM envelopes
+         ORDER BY name",
+    )?;
+
+    let rows = stmt.query_map([], |row| {
+        Ok(Envelope {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            tag: Tag::new(&row.get::<_, String>(2)?),
+        })
+    })?;
+
+    rows.collect()
+}
+
+pub fn add_envelope(conn: &Connection, name: &str, tag: &Tag) -> Result<()> {
+    conn.execute(
+        "INSERT INTO envelopes (name, tag) VALUES (?1, ?2)",
+        (name, tag.as_str()),
+    )?;
+
     Ok(())
-}
\ No newline at end of file
+}
+
+pub fn delete_envelope(conn: &Connection, id: i32) -> Result<()> {
+    // Foreign keys are off by default in SQLite, so remove assignments explicitly
+    conn.execute("DELETE FROM envelope_assignments WHERE envelope_id = ?1", [id])?;
+    conn.execute("DELETE FROM envelopes WHERE id = ?1", [id])?;
+    Ok(())
+}
+
+pub fn get_envelope_assignments(conn: &Connection) -> Result<Vec<EnvelopeAssignment>> {
+    let mut stmt = conn.prepare(
+        "SELECT envelope_id, month, amount
+         FROM envelope_assignments
+         ORDER BY month",
+    )?;
+
+    let rows = stmt.query_map([], |row| {
+        Ok(EnvelopeAssignment {
+            envelope_id: row.get(0)?,
+            month: row.get(1)?,
+            amount: row.get(2)?,
+        })
+    })?;
+
+    rows.collect()
+}
+
+// Set (not add to) the amount assigned to an envelope for a month
+pub fn set_envelope_assignment(
+    conn: &Connection,
+    envelope_id: i32,
+    month: &str,
+    amount: f64,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO envelope_assignments (envelope_id, month, amount)
+         VALUES (?1, ?2, ?3)
+         ON CONFLICT(envelope_id, month) DO UPDATE SET amount = excluded.amount",
+        (envelope_id, month, amount),
+    )?;
+
+    Ok(())
+}
+
+// Savings goal functions
+pub fn get_goals(conn: &Connection) -> Result<Vec<Goal>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, name, target, target_date, tag
+         FROM goals
+         ORDER BY target_date",
+    )?;
+
+    let rows = stmt.query_map([], |row| {
+        Ok(Goal {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            target: row.get(2)?,
+            target_date: row.get(3)?,
+            tag: Tag::new(&row.get::<_, String>(4)?),
+        })
+    })?;
+
+    rows.collect()
+}
+
+pub fn add_goal(
+    conn: &Connection,
+    name: &str,
+    target: f64,
+    target_date: &str,
+    tag: &Tag,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO goals (name, target, target_date, tag) VALUES (?1, ?2, ?3, ?4)",
+        (name, target, target_date, tag.as_str()),
+    )?;
+
+    Ok(())
+}
+
+pub fn delete_goal(conn: &Connection, id: i32) -> Result<()> {
+    conn.execute("DELETE FROM goals WHERE id = ?1", [id])?;
+    Ok(())
+}
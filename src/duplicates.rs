@@ -0,0 +1,324 @@
+use std::collections::HashSet;
+
+use chrono::NaiveDate;
+use rusqlite::Connection;
+use serde::{ Deserialize, Serialize };
+
+use crate::{
+    db,
+    hooks::{ self, Event, Hooks },
+    import::ImportRow,
+    models::{ Transaction, TransactionType },
+};
+
+/// How close two transactions must be to count as the same payment
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DuplicateTolerance {
+    // Largest gap between the two dates, in days
+    #[serde(default = "default_max_days")]
+    pub max_days: i64,
+    // Largest absolute difference between the two amounts
+    #[serde(default)]
+    pub amount: f64,
+    // Minimum source similarity, from 0 (anything) to 1 (identical)
+    #[serde(default = "default_min_similarity")]
+    pub min_similarity: f64,
+}
+
+fn default_max_days() -> i64 {
+    3
+}
+
+fn default_min_similarity() -> f64 {
+    0.6
+}
+
+impl Default for DuplicateTolerance {
+    fn default() -> Self {
+        Self {
+            max_days: default_max_days(),
+            amount: 0.0,
+            min_similarity: default_min_similarity(),
+        }
+    }
+}
+
+/// Two stored transactions that look like the same payment; `first` is the older entry
+#[derive(Debug, Clone)]
+pub struct DuplicatePair {
+    pub first: Transaction,
+    pub second: Transaction,
+    pub similarity: f64,
+}
+
+/// What to do with a pair under review
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    Merge,
+    KeepBoth,
+    DeleteFirst,
+    DeleteSecond,
+}
+
+/// The fields compared when looking for duplicates
+struct Entry<'a> {
+    source: &'a str,
+    amount: f64,
+    kind: TransactionType,
+    date: NaiveDate,
+}
+
+impl<'a> Entry<'a> {
+    fn from_transaction(tx: &'a Transaction) -> Option<Self> {
+        Some(Self {
+            source: &tx.source,
+            amount: tx.amount,
+            kind: tx.kind,
+            date: NaiveDate::parse_from_str(&tx.date, "%Y-%m-%d").ok()?,
+        })
+    }
+}
+
+// ============================================================================
+// Detection functions
+// ============================================================================
+
+/// Likely duplicate pairs among stored transactions, skipping pairs already
+/// reviewed and kept
+pub fn find_duplicates(
+    transactions: &[Transaction],
+    tolerance: &DuplicateTolerance,
+    dismissed: &HashSet<(i32, i32)>
+) -> Vec<DuplicatePair> {
+    let mut dated: Vec<(Entry, &Transaction)> = transactions
+        .iter()
+        .filter_map(|tx| Entry::from_transaction(tx).map(|e| (e, tx)))
+        .collect();
+    dated.sort_by_key(|(e, tx)| (e.date, tx.id));
+
+    let mut pairs = Vec::new();
+
+    for (i, (a, tx_a)) in dated.iter().enumerate() {
+        for (b, tx_b) in &dated[i + 1..] {
+            if (b.date - a.date).num_days() > tolerance.max_days {
+                break;
+            }
+
+            let (first, second) = if tx_a.id < tx_b.id { (*tx_a, *tx_b) } else { (*tx_b, *tx_a) };
+            if dismissed.contains(&(first.id, second.id)) {
+                continue;
+            }
+
+            if let Some(similarity) = compare(a, b, tolerance) {
+                pairs.push(DuplicatePair {
+                    first: first.clone(),
+                    second: second.clone(),
+                    similarity,
+                });
+            }
+        }
+    }
+
+    pairs
+}
+
+/// An existing transaction that the given one would duplicate
+pub fn find_match<'a>(
+    transactions: &'a [Transaction],
+    source: &str,
+    amount: f64,
+    kind: TransactionType,
+    date: &str,
+    tolerance: &DuplicateTolerance
+) -> Option<&'a Transaction> {
+    let entry = Entry {
+        source,
+        amount,
+        kind,
+        date: NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?,
+    };
+
+    transactions
+        .iter()
+        .filter_map(|tx| {
+            let existing = Entry::from_transaction(tx)?;
+            compare(&entry, &existing, tolerance).map(|similarity| (tx, similarity))
+        })
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(tx, _)| tx)
+}
+
+/// Skip import rows that match a transaction already in the database
+pub fn mark_import_duplicates(
+    rows: &mut [ImportRow],
+    transactions: &[Transaction],
+    tolerance: &DuplicateTolerance
+) {
+    for row in rows.iter_mut().filter(|r| !r.skip) {
+        let existing = find_match(
+            transactions,
+            &row.source,
+            row.amount,
+            row.kind,
+            &row.date,
+            tolerance
+        );
+
+        if let Some(tx) = existing {
+            row.skip = true;
+            row.note = Some(format!("possible duplicate of {} on {}", tx.source, tx.date));
+        }
+    }
+}
+
+/// Source similarity if `a` and `b` are within tolerance of each other
+fn compare(a: &Entry, b: &Entry, tolerance: &DuplicateTolerance) -> Option<f64> {
+    if a.kind != b.kind {
+        return None;
+    }
+    // Small epsilon so an exact tolerance survives float rounding
+    if (a.amount - b.amount).abs() > tolerance.amount + 0.005 {
+        return None;
+    }
+    if (a.date - b.date).num_days().abs() > tolerance.max_days {
+        return None;
+    }
+
+    let similarity = source_similarity(a.source, b.source);
+    (similarity >= tolerance.min_similarity).then_some(similarity)
+}
+
+/// Dice coefficient over character bigrams of the normalised sources. One
+/// source containing the other ("AMAZON" vs "AMAZON MKTPLACE 1234") counts as a match.
+pub fn source_similarity(a: &str, b: &str) -> f64 {
+    let a = normalize(a);
+    let b = normalize(b);
+
+    if a.is_empty() || b.is_empty() {
+        return if a == b { 1.0 } else { 0.0 };
+    }
+    if a.contains(&b) || b.contains(&a) {
+        return 1.0;
+    }
+
+    let bigrams = |s: &str| -> Vec<(char, char)> {
+        let chars: Vec<char> = s.chars().collect();
+        chars.windows(2).map(|w| (w[0], w[1])).collect()
+    };
+
+    let left = bigrams(&a);
+    let mut right = bigrams(&b);
+    let total = left.len() + right.len();
+    if total == 0 {
+        return 0.0;
+    }
+
+    let mut shared = 0;
+    for pair in &left {
+        if let Some(pos) = right.iter().position(|p| p == pair) {
+            right.swap_remove(pos);
+            shared += 1;
+        }
+    }
+
+    (2 * shared) as f64 / total as f64
+}
+
+fn normalize(source: &str) -> String {
+    source
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+// ============================================================================
+// Resolution
+// ============================================================================
+
+/// Apply the user's choice for a pair. Merging keeps `first` and folds
+/// `second` into it. Fires `transaction_updated`/`transaction_deleted` for the
+/// rows touched and returns the hooks that failed to start.
+pub fn resolve(conn: &Connection, pair: &DuplicatePair, resolution: Resolution, hooks: &Hooks) -> Result<Vec<String>, String> {
+    let warnings = match resolution {
+        Resolution::Merge => {
+            let merged = db::merge_transactions(conn, &pair.first, &pair.second).map_err(|e| e.to_string())?;
+            hooks::fire_all(hooks, &[
+                Event::TransactionUpdated { before: &pair.first, after: &merged },
+                Event::TransactionDeleted(&pair.second),
+            ])
+        }
+        Resolution::KeepBoth => {
+            db::dismiss_duplicate(conn, pair.first.id, pair.second.id).map_err(|e| e.to_string())?;
+            Vec::new()
+        }
+        Resolution::DeleteFirst | Resolution::DeleteSecond => {
+            let removed = if resolution == Resolution::DeleteFirst { &pair.first } else { &pair.second };
+            db::delete_transaction(conn, removed.id).map_err(|e| e.to_string())?;
+            hooks::fire(hooks, &Event::TransactionDeleted(removed))
+        }
+    };
+
+    Ok(warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ hooks::recording, models::Tag };
+    use std::path::Path;
+
+    /// Two coffees stored twice, the second with a note
+    fn pair(conn: &Connection) -> DuplicatePair {
+        for notes in ["", "card"] {
+            db::add_transaction(conn, "Coffee", 5.0, TransactionType::Debit, &Tag::new("food"), "2026-10-01").unwrap();
+            db::set_transaction_notes(conn, conn.last_insert_rowid(), notes).unwrap();
+        }
+        let mut transactions = db::get_transactions(conn).unwrap();
+        transactions.sort_by_key(|tx| tx.id);
+
+        DuplicatePair {
+            second: transactions.pop().unwrap(),
+            first: transactions.pop().unwrap(),
+            similarity: 1.0,
+        }
+    }
+
+    #[test]
+    fn resolving_fires_hooks_for_the_rows_touched() {
+        let cases = [
+            (Resolution::Merge, vec!["transaction_deleted", "transaction_updated"], 1),
+            (Resolution::DeleteFirst, vec!["transaction_deleted"], 1),
+            (Resolution::DeleteSecond, vec!["transaction_deleted"], 1),
+            (Resolution::KeepBoth, vec![], 2),
+        ];
+
+        for (resolution, expected, remaining) in cases {
+            let conn = db::open_db(Path::new(":memory:")).unwrap();
+            let (hooks, log) = recording::hooks(&format!("duplicates-{:?}", resolution));
+            let pair = pair(&conn);
+
+            let warnings = resolve(&conn, &pair, resolution, &hooks).unwrap();
+
+            assert!(warnings.is_empty());
+            assert_eq!(recording::events(&log, expected.len()), expected, "{:?}", resolution);
+            assert_eq!(db::get_transactions(&conn).unwrap().len(), remaining, "{:?}", resolution);
+        }
+    }
+
+    #[test]
+    fn merge_keeps_the_first_row_with_both_notes() {
+        let conn = db::open_db(Path::new(":memory:")).unwrap();
+        let pair = pair(&conn);
+
+        resolve(&conn, &pair, Resolution::Merge, &Hooks::default()).unwrap();
+
+        let remaining = db::get_transactions(&conn).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, pair.first.id);
+        assert_eq!(remaining[0].notes, "card");
+    }
+}
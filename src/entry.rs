@@ -0,0 +1,309 @@
+use chrono::{ Datelike, Local, NaiveDate };
+use rusqlite::Connection;
+use serde::{ Deserialize, Serialize };
+
+use crate::{
+    budget::{ self, BudgetStatus, Budgets },
+    config::Config,
+    db,
+    duplicates,
+    hooks::{ self, Event },
+    models::{ RecurringEntry, Tag, Transaction, TransactionType },
+    rules::{ RuleInput, RuleSet },
+};
+
+/// A transaction entered outside the TUI form (command line, HTTP API, library)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewEntry {
+    pub source: String,
+    pub amount: f64,
+    pub kind: TransactionType,
+    pub tag: String,
+    // YYYY-MM-DD, today when missing
+    pub date: Option<String>,
+    #[serde(default)]
+    pub notes: String,
+    // Save even when `budget_hard_limit` is on and the entry is over a budget,
+    // i.e. the user has confirmed it
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AddedEntry {
+    pub transaction: Transaction,
+    // Budgets over their limit after this entry; the ones it pushed over
+    // (`exceeded_now`) were recorded as budget events
+    pub breaches: Vec<BudgetStatus>,
+    pub duplicate: Option<Transaction>,
+    // Problems that didn't stop the entry, e.g. invalid rules
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdatedEntry {
+    pub before: Transaction,
+    pub transaction: Transaction,
+    // Same as `AddedEntry::breaches`, with the old amount no longer counted
+    pub breaches: Vec<BudgetStatus>,
+    // Hook failures
+    pub warnings: Vec<String>,
+}
+
+/// Validate and save an entry with the same checks as the TUI form: configured
+/// tags, categorisation rules (when `apply_rules_to_manual` is on), budget
+/// alerts and duplicate detection. With `budget_hard_limit` on, an expense over
+/// a budget is refused unless `force` is set.
+pub fn add_entry(conn: &Connection, config: &Config, entry: NewEntry) -> Result<AddedEntry, String> {
+    let tags = configured_tags(config);
+    let (date, mut tag) = validate(config, &entry)?;
+
+    let mut source = entry.source.clone();
+    let mut notes = entry.notes.clone();
+    let mut warnings = Vec::new();
+
+    if config.apply_rules_to_manual {
+        let (rules, errors) = RuleSet::new(&config.rules);
+        warnings.extend(errors);
+
+        let input = RuleInput { source: &entry.source, amount: entry.amount, kind: entry.kind, account: None };
+        if let Some(outcome) = rules.apply(&input, &tags) {
+            tag = outcome.tag.unwrap_or(tag);
+            source = outcome.source.unwrap_or(source);
+            if notes.is_empty() {
+                notes = outcome.notes.unwrap_or_default();
+            }
+        }
+    }
+
+    let date_text = date.format("%Y-%m-%d").to_string();
+    let transactions = db::get_transactions(conn).map_err(|e| e.to_string())?;
+
+    let breaches = check_budgets(config, &transactions, &entry, &tag, date, None)?;
+    let duplicate = duplicates
+        ::find_match(&transactions, &source, entry.amount, entry.kind, &date_text, &config.duplicates)
+        .cloned();
+
+    db::add_transaction(conn, &source, entry.amount, entry.kind, &tag, &date_text).map_err(|e| e.to_string())?;
+    let id = conn.last_insert_rowid();
+    if !notes.is_empty() {
+        db::set_transaction_notes(conn, id, &notes).map_err(|e| e.to_string())?;
+    }
+
+    let transaction = Transaction {
+        id: id as i32,
+        source,
+        amount: entry.amount,
+        kind: entry.kind,
+        tag,
+        date: date_text,
+        notes,
+    };
+
+    warnings.extend(hooks::fire(&config.hooks, &Event::TransactionAdded(&transaction)));
+    warnings.extend(record_breaches(conn, config, &breaches, &transaction)?);
+
+    Ok(AddedEntry {
+        transaction,
+        breaches,
+        duplicate,
+        warnings,
+    })
+}
+
+/// Overwrite transaction `id` with `entry`, validated and budget-checked like a
+/// new entry. Rules and duplicate detection only apply to new entries, as in
+/// the TUI's edit form.
+pub fn update_entry(conn: &Connection, config: &Config, id: i32, entry: NewEntry) -> Result<UpdatedEntry, String> {
+    let transactions = db::get_transactions(conn).map_err(|e| e.to_string())?;
+    let before = transactions
+        .iter()
+        .find(|tx| tx.id == id)
+        .cloned()
+        .ok_or_else(|| format!("no transaction with id {}", id))?;
+
+    let (date, tag) = validate(config, &entry)?;
+    let breaches = check_budgets(config, &transactions, &entry, &tag, date, Some(id))?;
+
+    let transaction = Transaction {
+        id,
+        source: entry.source,
+        amount: entry.amount,
+        kind: entry.kind,
+        tag,
+        date: date.format("%Y-%m-%d").to_string(),
+        notes: entry.notes,
+    };
+    db::update_transaction(
+        conn,
+        id,
+        &transaction.source,
+        transaction.amount,
+        transaction.kind,
+        &transaction.tag,
+        &transaction.date
+    ).map_err(|e| e.to_string())?;
+    db::set_transaction_notes(conn, id as i64, &transaction.notes).map_err(|e| e.to_string())?;
+
+    let mut warnings = hooks::fire(&config.hooks, &Event::TransactionUpdated { before: &before, after: &transaction });
+    warnings.extend(record_breaches(conn, config, &breaches, &transaction)?);
+
+    Ok(UpdatedEntry {
+        before,
+        transaction,
+        breaches,
+        warnings,
+    })
+}
+
+/// Add a monthly entry falling due on `day`. A day that has already passed
+/// this month counts as posted, so the first occurrence is the next due date.
+pub fn add_recurring(
+    conn: &Connection,
+    config: &Config,
+    source: &str,
+    amount: f64,
+    kind: TransactionType,
+    tag: &str,
+    day: u32
+) -> Result<RecurringEntry, String> {
+    if !amount.is_finite() || amount <= 0.0 {
+        return Err("amount must be a positive number".into());
+    }
+    if source.trim().is_empty() {
+        return Err("source must not be empty".into());
+    }
+    if !(1..=31).contains(&day) {
+        return Err("day must be between 1 and 31".into());
+    }
+    let tag = Tag::new(tag);
+    if !configured_tags(config).contains(&tag) {
+        return Err(format!("unknown tag '{}' (configured: {})", tag.as_str(), config.tags.join(", ")));
+    }
+
+    let today = Local::now().date_naive();
+    let last_month = if day < today.day() { today.format("%Y-%m").to_string() } else { String::new() };
+
+    db::add_recurring_entry(conn, source, amount, kind, &tag, day, &last_month).map_err(|e| e.to_string())?;
+    let id = conn.last_insert_rowid() as i32;
+
+    db::get_recurring_entries(conn)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|e| e.id == id)
+        .ok_or_else(|| "recurring entry was not saved".into())
+}
+
+fn configured_tags(config: &Config) -> Vec<Tag> {
+    config.tags
+        .iter()
+        .map(|t| Tag::new(t))
+        .collect()
+}
+
+/// The entry's date (today when missing) and tag, after checking it has a
+/// positive amount, a source and one of the configured tags
+fn validate(config: &Config, entry: &NewEntry) -> Result<(NaiveDate, Tag), String> {
+    let date = match &entry.date {
+        Some(date) =>
+            NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| format!("invalid date '{}', expected YYYY-MM-DD", date))?,
+        None => Local::now().date_naive(),
+    };
+    if !entry.amount.is_finite() || entry.amount <= 0.0 {
+        return Err("amount must be a positive number".into());
+    }
+    if entry.source.trim().is_empty() {
+        return Err("source must not be empty".into());
+    }
+
+    let tag = Tag::new(&entry.tag);
+    if !configured_tags(config).contains(&tag) {
+        return Err(format!("unknown tag '{}' (configured: {})", entry.tag, config.tags.join(", ")));
+    }
+
+    Ok((date, tag))
+}
+
+/// Budgets an expense leaves over their limit, refused under a hard limit
+/// unless the entry is forced
+fn check_budgets(
+    config: &Config,
+    transactions: &[Transaction],
+    entry: &NewEntry,
+    tag: &Tag,
+    date: NaiveDate,
+    replacing: Option<i32>
+) -> Result<Vec<BudgetStatus>, String> {
+    if entry.kind != TransactionType::Debit {
+        return Ok(Vec::new());
+    }
+
+    let budgets = Budgets::from_config(config);
+    let breaches = budget::check_entry(transactions, &budgets, tag, entry.amount, date, replacing);
+    if budgets.hard_limit && !breaches.is_empty() && !entry.force {
+        let message = budget::describe_breaches(&breaches, &config.currency);
+        return Err(format!("{} (budget_hard_limit is on; force the entry to save it anyway)", message));
+    }
+
+    Ok(breaches)
+}
+
+/// Alerts fire once, for the entry that crosses the limit: a budget event and
+/// a `budget_exceeded` hook each. Returns hook failures.
+fn record_breaches(
+    conn: &Connection,
+    config: &Config,
+    breaches: &[BudgetStatus],
+    transaction: &Transaction
+) -> Result<Vec<String>, String> {
+    let mut warnings = Vec::new();
+
+    for status in breaches.iter().filter(|b| b.exceeded_now) {
+        db::add_budget_event(
+            conn,
+            &transaction.date[..7],
+            status.tag.as_ref(),
+            status.limit,
+            status.spent,
+            &transaction.source
+        ).map_err(|e| e.to_string())?;
+        warnings.extend(hooks::fire(&config.hooks, &Event::BudgetExceeded { status, transaction }));
+    }
+
+    Ok(warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn coffee(amount: f64) -> NewEntry {
+        NewEntry {
+            source: "Coffee".into(),
+            amount,
+            kind: TransactionType::Debit,
+            tag: "food".into(),
+            date: Some("2026-10-01".into()),
+            notes: String::new(),
+            force: false,
+        }
+    }
+
+    #[test]
+    fn amounts_must_be_finite_and_positive() {
+        let conn = db::open_db(Path::new(":memory:")).unwrap();
+        let config = Config::default();
+
+        for amount in [f64::INFINITY, f64::NEG_INFINITY, f64::NAN, 0.0, -5.0] {
+            assert!(add_entry(&conn, &config, coffee(amount)).is_err(), "{} was accepted", amount);
+            assert!(add_recurring(&conn, &config, "Rent", amount, TransactionType::Debit, "bills", 1).is_err());
+        }
+        assert!(db::get_transactions(&conn).unwrap().is_empty());
+        assert!(db::get_recurring_entries(&conn).unwrap().is_empty());
+
+        let added = add_entry(&conn, &config, coffee(3.5)).unwrap();
+        assert!(update_entry(&conn, &config, added.transaction.id, coffee(f64::INFINITY)).is_err());
+        assert_eq!(db::get_transactions(&conn).unwrap()[0].amount, 3.5);
+    }
+}
@@ -0,0 +1,94 @@
+use std::collections::BTreeMap;
+
+use chrono::{ Datelike, Local, NaiveDate };
+
+use crate::models::{ Envelope, EnvelopeAssignment, Transaction, TransactionType };
+
+/// One month of an envelope's ledger
+#[derive(Debug, Clone)]
+pub struct EnvelopeMonth {
+    pub month: String,
+    pub carried_in: f64, // Rolled over from previous months; negative when overspent
+    pub assigned: f64,
+    pub spent: f64,
+    pub available: f64,
+}
+
+// ============================================================================
+// Ledger functions
+// ============================================================================
+
+/// Build the month-by-month ledger of an envelope up to and including `until`.
+/// Unused money and overspending both roll over into the next month.
+pub fn envelope_ledger(
+    envelope: &Envelope,
+    assignments: &[EnvelopeAssignment],
+    transactions: &[Transaction],
+    until: &str
+) -> Vec<EnvelopeMonth> {
+    let mut months: BTreeMap<String, (f64, f64)> = BTreeMap::new();
+
+    for a in assignments.iter().filter(|a| a.envelope_id == envelope.id) {
+        months.entry(a.month.clone()).or_insert((0.0, 0.0)).0 += a.amount;
+    }
+
+    for tx in transactions
+        .iter()
+        .filter(|tx| tx.kind == TransactionType::Debit && tx.tag == envelope.tag) {
+        if tx.date.len() >= 7 {
+            months.entry(tx.date[..7].to_string()).or_insert((0.0, 0.0)).1 += tx.amount;
+        }
+    }
+
+    // Always include the requested month so it shows what was carried in
+    months.entry(until.to_string()).or_insert((0.0, 0.0));
+
+    let mut ledger = Vec::new();
+    let mut available = 0.0;
+
+    for (month, (assigned, spent)) in months.into_iter().filter(|(m, _)| m.as_str() <= until) {
+        let carried_in = available;
+        available = carried_in + assigned - spent;
+
+        ledger.push(EnvelopeMonth {
+            month,
+            carried_in,
+            assigned,
+            spent,
+            available,
+        });
+    }
+
+    ledger
+}
+
+/// Income received up to `until` that hasn't been assigned to an envelope yet
+pub fn ready_to_assign(
+    assignments: &[EnvelopeAssignment],
+    transactions: &[Transaction],
+    until: &str
+) -> f64 {
+    let income: f64 = transactions
+        .iter()
+        .filter(|tx| tx.kind == TransactionType::Credit && tx.date.len() >= 7 && &tx.date[..7] <= until)
+        .map(|tx| tx.amount)
+        .sum();
+
+    let assigned: f64 = assignments
+        .iter()
+        .filter(|a| a.month.as_str() <= until)
+        .map(|a| a.amount)
+        .sum();
+
+    income - assigned
+}
+
+/// Move a "YYYY-MM" month forwards or backwards
+pub fn shift_month(month: &str, delta: i32) -> String {
+    let date = NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d").unwrap_or_else(|_|
+        Local::now().date_naive()
+    );
+
+    let total = date.year() * 12 + (date.month0() as i32) + delta;
+    format!("{:04}-{:02}", total.div_euclid(12), total.rem_euclid(12) + 1)
+}
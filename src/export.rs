@@ -0,0 +1,86 @@
+pub mod csv;
+pub mod html;
+pub mod journal;
+
+use crate::{ budget::Budgets, models::{ RecurringEntry, Transaction } };
+
+use self::{ csv::CsvExportOptions, journal::Dialect };
+
+/// File formats the export screen can write
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Html,
+    Ledger,
+    Hledger,
+    Beancount,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 5] = [
+        ExportFormat::Csv,
+        ExportFormat::Html,
+        ExportFormat::Ledger,
+        ExportFormat::Hledger,
+        ExportFormat::Beancount,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Html => "html",
+            ExportFormat::Ledger => "ledger",
+            ExportFormat::Hledger => "hledger",
+            ExportFormat::Beancount => "beancount",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|f| f.name() == name)
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Html => "html",
+            ExportFormat::Ledger => "ledger",
+            ExportFormat::Hledger => "journal",
+            ExportFormat::Beancount => "beancount",
+        }
+    }
+
+    /// Where the export screen suggests saving this format
+    pub fn default_path(&self) -> String {
+        format!("~/fitui.{}", self.extension())
+    }
+}
+
+/// Everything besides the selected transactions that an export may need
+pub struct ExportContext<'a> {
+    pub recurring: &'a [RecurringEntry],
+    pub currency: &'a str,
+    pub csv: &'a CsvExportOptions,
+    pub budgets: &'a Budgets,
+}
+
+/// Render the selected transactions in `format`. Journal formats also carry
+/// the recurring entries; the HTML report includes budget status.
+pub fn render(
+    format: ExportFormat,
+    transactions: &[Transaction],
+    context: &ExportContext
+) -> Result<String, String> {
+    let dialect = match format {
+        ExportFormat::Csv => {
+            return csv::write_csv(transactions, context.csv);
+        }
+        ExportFormat::Html => {
+            return Ok(html::write_html(transactions, context.budgets, context.currency));
+        }
+        ExportFormat::Ledger => Dialect::Ledger,
+        ExportFormat::Hledger => Dialect::Hledger,
+        ExportFormat::Beancount => Dialect::Beancount,
+    };
+
+    Ok(journal::write_journal(dialect, transactions, context.recurring, context.currency))
+}
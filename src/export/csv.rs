@@ -0,0 +1,114 @@
+use std::fmt::Write;
+
+use chrono::NaiveDate;
+use serde::{ Deserialize, Serialize };
+
+use crate::models::{ Transaction, TransactionType };
+
+/// Columns a CSV export can contain
+pub const COLUMNS: [&str; 8] = ["id", "date", "source", "amount", "signed_amount", "type", "tag", "notes"];
+
+/// CSV export layout, configured under `csv_export` in config.yaml
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CsvExportOptions {
+    #[serde(default = "default_columns")]
+    pub columns: Vec<String>,
+    // chrono format string, e.g. "%d/%m/%Y"
+    #[serde(default = "default_date_format")]
+    pub date_format: String,
+    #[serde(default = "default_decimal_separator")]
+    pub decimal_separator: char,
+    #[serde(default = "default_delimiter")]
+    pub delimiter: char,
+}
+
+fn default_columns() -> Vec<String> {
+    ["date", "source", "amount", "type", "tag", "notes"]
+        .iter()
+        .map(|c| c.to_string())
+        .collect()
+}
+
+fn default_date_format() -> String {
+    "%Y-%m-%d".into()
+}
+
+fn default_decimal_separator() -> char {
+    '.'
+}
+
+fn default_delimiter() -> char {
+    ','
+}
+
+impl Default for CsvExportOptions {
+    fn default() -> Self {
+        Self {
+            columns: default_columns(),
+            date_format: default_date_format(),
+            decimal_separator: default_decimal_separator(),
+            delimiter: default_delimiter(),
+        }
+    }
+}
+
+/// Write transactions, oldest first, with a header row
+pub fn write_csv(transactions: &[Transaction], options: &CsvExportOptions) -> Result<String, String> {
+    if let Some(unknown) = options.columns.iter().find(|c| !COLUMNS.contains(&c.as_str())) {
+        return Err(format!("Unknown CSV column '{}' (available: {})", unknown, COLUMNS.join(", ")));
+    }
+    if !options.delimiter.is_ascii() {
+        return Err(format!("CSV delimiter '{}' must be a single ASCII character", options.delimiter));
+    }
+
+    let mut sorted: Vec<&Transaction> = transactions.iter().collect();
+    sorted.sort_by(|a, b| a.date.cmp(&b.date).then(a.id.cmp(&b.id)));
+
+    let mut writer = ::csv::WriterBuilder::new()
+        .delimiter(options.delimiter as u8)
+        .from_writer(Vec::new());
+
+    writer.write_record(&options.columns).map_err(|e| e.to_string())?;
+
+    for tx in sorted {
+        let record: Vec<String> = options.columns
+            .iter()
+            .map(|column| field(tx, column, options))
+            .collect::<Result<_, _>>()?;
+        writer.write_record(&record).map_err(|e| e.to_string())?;
+    }
+
+    let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+fn field(tx: &Transaction, column: &str, options: &CsvExportOptions) -> Result<String, String> {
+    let amount = |value: f64| format!("{:.2}", value).replace('.', &options.decimal_separator.to_string());
+
+    Ok(match column {
+        "id" => tx.id.to_string(),
+        "date" => format_date(&tx.date, &options.date_format)?,
+        "source" => tx.source.clone(),
+        "amount" => amount(tx.amount),
+        "signed_amount" =>
+            amount(match tx.kind {
+                TransactionType::Credit => tx.amount,
+                TransactionType::Debit => -tx.amount,
+            }),
+        "type" => tx.kind.as_str().to_string(),
+        "tag" => tx.tag.as_str().to_string(),
+        "notes" => tx.notes.clone(),
+        _ => String::new(),
+    })
+}
+
+fn format_date(date: &str, format: &str) -> Result<String, String> {
+    let Ok(parsed) = NaiveDate::parse_from_str(date, "%Y-%m-%d") else {
+        return Ok(date.to_string());
+    };
+
+    // An invalid format string surfaces as a fmt error rather than a panic
+    let mut out = String::new();
+    write!(out, "{}", parsed.format(format)).map_err(|_| format!("Invalid date format '{}'", format))?;
+    Ok(out)
+}
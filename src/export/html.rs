@@ -0,0 +1,315 @@
+use std::fmt::Write;
+
+use crate::{
+    budget::{ BudgetReport, Budgets, WARNING_RATIO },
+    models::{ Transaction, TransactionType },
+    stats::{ calculate_earned, calculate_monthly_history, calculate_spent, calculate_spent_per_tag },
+};
+
+/// Number of largest transactions listed in the report
+const TOP_TRANSACTIONS: usize = 10;
+
+/// The colours of the TUI's default theme, as CSS
+struct Palette {
+    accent: &'static str,
+    accent_soft: &'static str,
+    credit: &'static str,
+    debit: &'static str,
+    warning: &'static str,
+    muted: &'static str,
+    subtle: &'static str,
+    background: &'static str,
+    surface: &'static str,
+    foreground: &'static str,
+}
+
+const PALETTE: Palette = Palette {
+    accent: "#64b5f6",
+    accent_soft: "#508cc8",
+    credit: "#66bb6a",
+    debit: "#ef5350",
+    warning: "#ffb74d",
+    muted: "#a0a0aa",
+    subtle: "#5a5a6e",
+    background: "#181824",
+    surface: "#222234",
+    foreground: "#dce1f5",
+};
+
+/// Self-contained HTML report: summary cards, monthly bars, tag breakdown,
+/// top transactions and budget status. Charts are inline SVG and styles are
+/// embedded, so the file can be opened anywhere without network access.
+pub fn write_html(transactions: &[Transaction], budgets: &Budgets, currency: &str) -> String {
+    let theme = &PALETTE;
+    let money = |value: f64| format!("{}{:.2}", escape(currency), value);
+
+    let mut dates: Vec<&str> = transactions
+        .iter()
+        .map(|tx| tx.date.as_str())
+        .collect();
+    dates.sort_unstable();
+    let period = match (dates.first(), dates.last()) {
+        (Some(first), Some(last)) => format!("{} – {}", first, last),
+        _ => "No transactions".to_string(),
+    };
+
+    let earned = calculate_earned(transactions);
+    let spent = calculate_spent(transactions);
+    let balance = earned - spent;
+
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>FiTui Report · {period}</title>
+<style>
+  body {{ background: {bg}; color: {fg}; font-family: -apple-system, "Segoe UI", Roboto, sans-serif; margin: 0; padding: 2rem; }}
+  main {{ max-width: 960px; margin: 0 auto; }}
+  h1 {{ color: {accent}; margin-bottom: 0; }}
+  h2 {{ color: {accent}; border-bottom: 1px solid {subtle}; padding-bottom: .3rem; margin-top: 2.5rem; }}
+  .period {{ color: {muted}; margin-top: .3rem; }}
+  .cards {{ display: grid; grid-template-columns: repeat(3, 1fr); gap: 1rem; margin-top: 1.5rem; }}
+  .card {{ background: {surface}; border: 1px solid {subtle}; border-radius: 10px; padding: 1rem; text-align: center; }}
+  .card.main {{ border-color: {accent}; }}
+  .card .label {{ color: {muted}; font-size: .85rem; letter-spacing: .08em; }}
+  .card .value {{ font-size: 1.6rem; font-weight: bold; margin-top: .5rem; }}
+  table {{ width: 100%; border-collapse: collapse; }}
+  td, th {{ padding: .45rem .6rem; text-align: left; border-bottom: 1px solid {surface}; }}
+  th {{ color: {muted}; font-weight: normal; }}
+  .num {{ text-align: right; font-variant-numeric: tabular-nums; }}
+  .muted {{ color: {muted}; }}
+  svg text {{ fill: {muted}; font-size: 12px; }}
+</style>
+</head>
+<body>
+<main>
+<h1>FiTui Report</h1>
+<div class="period">{period} · {count} transactions</div>
+<div class="cards">
+  <div class="card"><div class="label">↑ EARNED</div><div class="value" style="color:{credit}">{earned}</div></div>
+  <div class="card main"><div class="label">BALANCE</div><div class="value" style="color:{balance_color}">{balance}</div></div>
+  <div class="card"><div class="label">↓ SPENT</div><div class="value" style="color:{debit}">{spent}</div></div>
+</div>
+"#,
+        period = escape(&period),
+        count = transactions.len(),
+        bg = theme.background,
+        fg = theme.foreground,
+        accent = theme.accent,
+        subtle = theme.subtle,
+        muted = theme.muted,
+        surface = theme.surface,
+        credit = theme.credit,
+        debit = theme.debit,
+        balance_color = if balance >= 0.0 { theme.credit } else { theme.debit },
+        earned = money(earned),
+        spent = money(spent),
+        balance = money(balance)
+    );
+
+    html.push_str("<h2>Income &amp; Expenses by Month</h2>\n");
+    html.push_str(&monthly_chart(&calculate_monthly_history(transactions), theme));
+
+    html.push_str("<h2>Spending by Tag</h2>\n");
+    let mut per_tag: Vec<(String, f64)> = calculate_spent_per_tag(transactions)
+        .into_iter()
+        .map(|(tag, amount)| (tag.as_str().to_string(), amount))
+        .collect();
+    per_tag.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    html.push_str(&tag_chart(&per_tag, spent, currency, theme));
+
+    html.push_str("<h2>Top Transactions</h2>\n");
+    html.push_str(&top_transactions(transactions, currency, theme));
+
+    if let Some(last) = dates.last().and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok()) {
+        let report = BudgetReport::new(transactions, budgets, last);
+        if !report.is_empty() {
+            let _ = writeln!(html, "<h2>Budgets · {}</h2>", report.month);
+            html.push_str(&budget_table(&report, currency, theme));
+        }
+    }
+
+    let _ = write!(
+        html,
+        "<p class=\"muted\" style=\"margin-top:3rem\">Generated by FiTui on {}</p>\n</main>\n</body>\n</html>\n",
+        chrono::Local::now().format("%Y-%m-%d %H:%M")
+    );
+
+    html
+}
+
+/// Side-by-side income and expense bars per month
+fn monthly_chart(history: &[(String, f64, f64)], theme: &Palette) -> String {
+    if history.is_empty() {
+        return "<p class=\"muted\">No data.</p>\n".into();
+    }
+
+    let (width, height, bottom) = (900.0, 260.0, 30.0);
+    let plot = height - bottom - 10.0;
+    let max = history
+        .iter()
+        .fold(0.0_f64, |m, (_, earned, spent)| m.max(*earned).max(*spent))
+        .max(1.0);
+    let slot = width / (history.len() as f64);
+    let bar = (slot * 0.35).min(40.0);
+
+    let mut svg = format!(
+        "<svg viewBox=\"0 0 {w} {h}\" width=\"100%\" role=\"img\" aria-label=\"Monthly income and expenses\">\n",
+        w = width,
+        h = height
+    );
+    let _ = writeln!(
+        svg,
+        "<line x1=\"0\" y1=\"{y}\" x2=\"{w}\" y2=\"{y}\" stroke=\"{c}\"/>",
+        y = plot + 10.0,
+        w = width,
+        c = theme.subtle
+    );
+
+    for (i, (month, earned, spent)) in history.iter().enumerate() {
+        let center = slot * (i as f64) + slot / 2.0;
+        for (offset, value, color) in [(-bar, *earned, theme.credit), (0.0, *spent, theme.debit)] {
+            let h = (value / max) * plot;
+            let _ = writeln!(
+                svg,
+                "<rect x=\"{x:.1}\" y=\"{y:.1}\" width=\"{bw:.1}\" height=\"{h:.1}\" rx=\"2\" fill=\"{c}\"><title>{month}: {v:.2}</title></rect>",
+                x = center + offset,
+                y = plot + 10.0 - h,
+                bw = bar,
+                h = h,
+                c = color,
+                month = escape(month),
+                v = value
+            );
+        }
+        let _ = writeln!(
+            svg,
+            "<text x=\"{x:.1}\" y=\"{y}\" text-anchor=\"middle\">{m}</text>",
+            x = center,
+            y = height - 8.0,
+            m = escape(month)
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Horizontal bar per tag with its share of total spending
+fn tag_chart(per_tag: &[(String, f64)], total: f64, currency: &str, theme: &Palette) -> String {
+    if per_tag.is_empty() {
+        return "<p class=\"muted\">No spending in this period.</p>\n".into();
+    }
+
+    let row = 28.0;
+    let (label_width, bar_width) = (130.0, 560.0);
+    let height = row * (per_tag.len() as f64);
+    let max = per_tag[0].1.max(1.0);
+
+    let mut svg = format!(
+        "<svg viewBox=\"0 0 900 {h}\" width=\"100%\" role=\"img\" aria-label=\"Spending by tag\">\n",
+        h = height
+    );
+    for (i, (tag, amount)) in per_tag.iter().enumerate() {
+        let y = row * (i as f64);
+        let w = (amount / max) * bar_width;
+        let share = if total > 0.0 { (amount / total) * 100.0 } else { 0.0 };
+        let _ = writeln!(
+            svg,
+            "<text x=\"0\" y=\"{ty:.1}\">#{tag}</text>\
+             <rect x=\"{lx}\" y=\"{ry:.1}\" width=\"{w:.1}\" height=\"18\" rx=\"3\" fill=\"{c}\"/>\
+             <text x=\"{vx:.1}\" y=\"{ty:.1}\">{cur}{amount:.2} ({share:.1}%)</text>",
+            ty = y + 14.0,
+            ry = y + 1.0,
+            lx = label_width,
+            w = w,
+            c = theme.accent_soft,
+            vx = label_width + w + 8.0,
+            tag = escape(tag),
+            cur = escape(currency),
+            amount = amount,
+            share = share
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn top_transactions(transactions: &[Transaction], currency: &str, theme: &Palette) -> String {
+    let mut sorted: Vec<&Transaction> = transactions.iter().collect();
+    sorted.sort_by(|a, b| b.amount.partial_cmp(&a.amount).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut table = String::from(
+        "<table>\n<tr><th>Date</th><th>Source</th><th>Tag</th><th class=\"num\">Amount</th></tr>\n"
+    );
+    for tx in sorted.into_iter().take(TOP_TRANSACTIONS) {
+        let (sign, color) = match tx.kind {
+            TransactionType::Credit => ("+", theme.credit),
+            TransactionType::Debit => ("−", theme.debit),
+        };
+        let _ = writeln!(
+            table,
+            "<tr><td class=\"muted\">{}</td><td>{}</td><td class=\"muted\">#{}</td><td class=\"num\" style=\"color:{}\">{}{}{:.2}</td></tr>",
+            escape(&tx.date),
+            escape(&tx.source),
+            escape(tx.tag.as_str()),
+            color,
+            sign,
+            escape(currency),
+            tx.amount
+        );
+    }
+    table.push_str("</table>\n");
+    table
+}
+
+fn budget_table(report: &BudgetReport, currency: &str, theme: &Palette) -> String {
+    let mut table = String::from(
+        "<table>\n<tr><th>Budget</th><th class=\"num\">Spent</th><th class=\"num\">Limit</th><th>Used</th></tr>\n"
+    );
+
+    for status in report.overall.iter().chain(report.tags.iter()) {
+        let color = budget_color(status.ratio(), theme);
+        let percent = status.ratio() * 100.0;
+        let _ = writeln!(
+            table,
+            "<tr><td>{label}</td><td class=\"num\">{cur}{spent:.2}</td><td class=\"num\">{cur}{limit:.2}</td>\
+             <td><svg viewBox=\"0 0 200 12\" width=\"200\" height=\"12\"><rect width=\"200\" height=\"12\" rx=\"3\" fill=\"{track}\"/>\
+             <rect width=\"{w:.1}\" height=\"12\" rx=\"3\" fill=\"{c}\"/></svg> <span style=\"color:{c}\">{percent:.0}%</span></td></tr>",
+            label = escape(&status.label()),
+            cur = escape(currency),
+            spent = status.spent,
+            limit = status.limit,
+            track = theme.surface,
+            w = percent.min(100.0) * 2.0,
+            c = color,
+            percent = percent
+        );
+    }
+
+    table.push_str("</table>\n");
+    table
+}
+
+/// Same thresholds as the stats view's gauges
+fn budget_color(ratio: f64, theme: &Palette) -> &'static str {
+    if ratio >= 1.0 {
+        theme.debit
+    } else if ratio >= WARNING_RATIO {
+        theme.warning
+    } else {
+        theme.credit
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
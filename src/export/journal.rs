@@ -0,0 +1,241 @@
+use std::fmt::Write;
+
+use chrono::Local;
+
+use crate::models::{ RecurringEntry, Transaction, TransactionType };
+
+/// Balancing account for every exported transaction
+const CASH_ACCOUNT: &str = "Assets:Cash";
+
+/// Plain-text accounting dialects
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Dialect {
+    Ledger,
+    Hledger,
+    Beancount,
+}
+
+/// Render transactions and recurring entries as a journal. Tags become
+/// `Expenses:<tag>`/`Income:<tag>` accounts balanced against `Assets:Cash`.
+pub fn write_journal(
+    dialect: Dialect,
+    transactions: &[Transaction],
+    recurring: &[RecurringEntry],
+    currency: &str
+) -> String {
+    let mut sorted: Vec<&Transaction> = transactions.iter().collect();
+    sorted.sort_by(|a, b| a.date.cmp(&b.date).then(a.id.cmp(&b.id)));
+
+    match dialect {
+        Dialect::Ledger | Dialect::Hledger => write_ledger(dialect, &sorted, recurring, currency),
+        Dialect::Beancount => write_beancount(&sorted, recurring, currency),
+    }
+}
+
+fn write_ledger(
+    dialect: Dialect,
+    transactions: &[&Transaction],
+    recurring: &[RecurringEntry],
+    currency: &str
+) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "; Exported from FiTui on {}", Local::now().format("%Y-%m-%d"));
+
+    let active: Vec<&RecurringEntry> = recurring
+        .iter()
+        .filter(|e| e.active)
+        .collect();
+    if !active.is_empty() {
+        let _ = writeln!(out, "\n; Recurring entries");
+    }
+    for entry in active {
+        // Ledger's period expressions have no day-of-month, so keep it as a comment
+        let period = match dialect {
+            Dialect::Hledger => format!("every {} day of month", ordinal(entry.day)),
+            _ => "Monthly".to_string(),
+        };
+        let _ = writeln!(out, "\n~ {}  {}", period, payee(&entry.source));
+        if dialect == Dialect::Ledger {
+            let _ = writeln!(out, "    ; day: {}", entry.day);
+        }
+        let _ = writeln!(
+            out,
+            "    {:<36}{}",
+            category_account(entry.kind, entry.tag.as_str(), false),
+            ledger_amount(entry.kind, entry.amount, currency)
+        );
+        let _ = writeln!(out, "    {}", CASH_ACCOUNT);
+    }
+
+    for tx in transactions {
+        let _ = writeln!(out, "\n{} {}", tx.date, payee(&tx.source));
+        if !tx.notes.is_empty() {
+            let _ = writeln!(out, "    ; {}", single_line(&tx.notes));
+        }
+        let _ = writeln!(
+            out,
+            "    {:<36}{}",
+            category_account(tx.kind, tx.tag.as_str(), false),
+            ledger_amount(tx.kind, tx.amount, currency)
+        );
+        let _ = writeln!(out, "    {}", CASH_ACCOUNT);
+    }
+
+    out
+}
+
+fn write_beancount(
+    transactions: &[&Transaction],
+    recurring: &[RecurringEntry],
+    currency: &str
+) -> String {
+    let commodity = commodity_code(currency);
+    let mut out = String::new();
+    let _ = writeln!(out, "; Exported from FiTui on {}", Local::now().format("%Y-%m-%d"));
+    let _ = writeln!(out, "\noption \"operating_currency\" \"{}\"", commodity);
+
+    // Beancount needs every account opened before it is used
+    let opened = transactions
+        .first()
+        .map(|tx| tx.date.clone())
+        .unwrap_or_else(|| Local::now().format("%Y-%m-%d").to_string());
+
+    let mut accounts = vec![CASH_ACCOUNT.to_string()];
+    let categories = transactions
+        .iter()
+        .map(|tx| (tx.kind, tx.tag.as_str()))
+        .chain(recurring.iter().map(|e| (e.kind, e.tag.as_str())));
+    for (kind, tag) in categories {
+        let account = category_account(kind, tag, true);
+        if !accounts.contains(&account) {
+            accounts.push(account);
+        }
+    }
+    accounts.sort();
+
+    let _ = writeln!(out);
+    for account in &accounts {
+        let _ = writeln!(out, "{} open {}", opened, account);
+    }
+
+    // Beancount has no periodic transactions; keep them as custom directives
+    let active: Vec<&RecurringEntry> = recurring
+        .iter()
+        .filter(|e| e.active)
+        .collect();
+    if !active.is_empty() {
+        let _ = writeln!(out);
+    }
+    for entry in active {
+        let _ = writeln!(
+            out,
+            "{} custom \"fitui-recurring\" \"{}\" {} {:.2} {} {}",
+            opened,
+            escape(&entry.source),
+            category_account(entry.kind, entry.tag.as_str(), true),
+            signed(entry.kind, entry.amount),
+            commodity,
+            entry.day
+        );
+    }
+
+    for tx in transactions {
+        let _ = writeln!(out, "\n{} * \"{}\"", tx.date, escape(&tx.source));
+        if !tx.notes.is_empty() {
+            let _ = writeln!(out, "  note: \"{}\"", escape(&tx.notes));
+        }
+        let _ = writeln!(
+            out,
+            "  {:<36}{:>12.2} {}",
+            category_account(tx.kind, tx.tag.as_str(), true),
+            signed(tx.kind, tx.amount),
+            commodity
+        );
+        let _ = writeln!(out, "  {}", CASH_ACCOUNT);
+    }
+
+    out
+}
+
+/// Category postings are positive for spending and negative for income,
+/// so the cash posting that balances them has the opposite sign
+fn signed(kind: TransactionType, amount: f64) -> f64 {
+    match kind {
+        TransactionType::Debit => amount,
+        TransactionType::Credit => -amount,
+    }
+}
+
+fn ledger_amount(kind: TransactionType, amount: f64, currency: &str) -> String {
+    let value = signed(kind, amount);
+    let sign = if value < 0.0 { "-" } else { "" };
+    format!("{}{}{:.2}", sign, currency, value.abs())
+}
+
+/// `Expenses:food` / `Income:salary`; beancount components must be capitalised
+fn category_account(kind: TransactionType, tag: &str, beancount: bool) -> String {
+    let root = match kind {
+        TransactionType::Debit => "Expenses",
+        TransactionType::Credit => "Income",
+    };
+
+    if !beancount {
+        return format!("{}:{}", root, tag);
+    }
+
+    let mut name: String = tag
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    match name.chars().next() {
+        Some(c) if c.is_ascii_alphabetic() => {
+            name[..1].make_ascii_uppercase();
+        }
+        _ => name.insert(0, 'X'),
+    }
+
+    format!("{}:{}", root, name)
+}
+
+/// ISO code for common currency symbols; beancount commodities must be upper-case names
+fn commodity_code(currency: &str) -> String {
+    match currency.trim() {
+        "$" => "USD".into(),
+        "₹" => "INR".into(),
+        "€" => "EUR".into(),
+        "£" => "GBP".into(),
+        "¥" => "JPY".into(),
+        other => {
+            let code: String = other
+                .chars()
+                .filter(|c| c.is_ascii_alphabetic())
+                .collect::<String>()
+                .to_uppercase();
+            if code.len() >= 2 { code } else { "CASH".into() }
+        }
+    }
+}
+
+fn ordinal(day: u32) -> String {
+    let suffix = match (day % 10, day % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", day, suffix)
+}
+
+fn single_line(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// A `;` would start a comment in the middle of a ledger payee
+fn payee(s: &str) -> String {
+    single_line(s).replace(';', ",")
+}
+
+fn escape(s: &str) -> String {
+    single_line(s).replace('\\', "\\\\").replace('"', "\\\"")
+}
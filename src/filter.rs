@@ -0,0 +1,85 @@
+use chrono::NaiveDate;
+
+use crate::models::{ Tag, Transaction, TransactionType };
+
+/// Selects transactions for exports and reports. Empty fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct TransactionFilter {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub tags: Vec<Tag>,
+    pub kind: Option<TransactionType>,
+    // Case-insensitive substring of the source or notes
+    pub search: Option<String>,
+}
+
+impl TransactionFilter {
+    pub fn matches(&self, tx: &Transaction) -> bool {
+        if self.from.is_some() || self.to.is_some() {
+            let Ok(date) = NaiveDate::parse_from_str(&tx.date, "%Y-%m-%d") else {
+                return false;
+            };
+            if self.from.is_some_and(|from| date < from) || self.to.is_some_and(|to| date > to) {
+                return false;
+            }
+        }
+
+        if !self.tags.is_empty() && !self.tags.contains(&tx.tag) {
+            return false;
+        }
+
+        if self.kind.is_some_and(|kind| kind != tx.kind) {
+            return false;
+        }
+
+        if let Some(search) = &self.search {
+            let needle = search.to_lowercase();
+            if !tx.source.to_lowercase().contains(&needle) && !tx.notes.to_lowercase().contains(&needle) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    pub fn apply(&self, transactions: &[Transaction]) -> Vec<Transaction> {
+        transactions
+            .iter()
+            .filter(|tx| self.matches(tx))
+            .cloned()
+            .collect()
+    }
+}
+
+/// `2026-10-01`, or a month (`2026-10`) or year (`2026`) taken as its first day
+/// (`end = false`) or last day (`end = true`)
+pub fn parse_date_bound(input: &str, end: bool) -> Result<NaiveDate, String> {
+    let input = input.trim();
+
+    if let Ok(date) = NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+        return Ok(date);
+    }
+
+    let parts: Vec<&str> = input.split('-').collect();
+    let year: i32 = parts[0].parse().map_err(|_| format!("invalid date '{}'", input))?;
+    let (start_month, end_month) = match parts.as_slice() {
+        [_] => (1, 12),
+        [_, month] => {
+            let month: u32 = month.parse().map_err(|_| format!("invalid date '{}'", input))?;
+            (month, month)
+        }
+        _ => {
+            return Err(format!("invalid date '{}'", input));
+        }
+    };
+
+    let first = NaiveDate::from_ymd_opt(year, start_month, 1).ok_or_else(|| format!("invalid date '{}'", input))?;
+    if !end {
+        return Ok(first);
+    }
+
+    let (next_year, next_month) = if end_month == 12 { (year + 1, 1) } else { (year, end_month + 1) };
+    NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .and_then(|d| d.pred_opt())
+        .ok_or_else(|| format!("invalid date '{}'", input))
+}
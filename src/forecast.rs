@@ -0,0 +1,120 @@
+use chrono::{ Datelike, Duration, NaiveDate };
+
+use crate::models::{ RecurringEntry, TransactionType };
+
+/// Projection lengths (in days) the forecast view cycles through
+pub const HORIZONS: [i64; 3] = [30, 90, 365];
+
+pub struct ForecastPoint {
+    pub date: NaiveDate,
+    pub balance: f64,
+}
+
+pub struct Forecast {
+    pub points: Vec<ForecastPoint>,
+    pub lowest: usize,
+    pub first_negative: Option<NaiveDate>,
+    pub upcoming: Vec<(NaiveDate, RecurringEntry)>,
+}
+
+impl Forecast {
+    pub fn new(start_balance: f64, entries: &[RecurringEntry], today: NaiveDate, days: i64) -> Self {
+        let end = today + Duration::days(days - 1);
+        let upcoming = scheduled_occurrences(entries, today, end);
+
+        let mut points = Vec::with_capacity(days as usize);
+        let mut balance = start_balance;
+        let mut next_event = 0;
+
+        for offset in 0..days {
+            let date = today + Duration::days(offset);
+
+            while next_event < upcoming.len() && upcoming[next_event].0 == date {
+                let entry = &upcoming[next_event].1;
+                match entry.kind {
+                    TransactionType::Credit => {
+                        balance += entry.amount;
+                    }
+                    TransactionType::Debit => {
+                        balance -= entry.amount;
+                    }
+                }
+                next_event += 1;
+            }
+
+            points.push(ForecastPoint { date, balance });
+        }
+
+        let lowest = points
+            .iter()
+            .enumerate()
+            .min_by(|a, b| a.1.balance.partial_cmp(&b.1.balance).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+
+        let first_negative = points
+            .iter()
+            .find(|p| p.balance < 0.0)
+            .map(|p| p.date);
+
+        Self {
+            points,
+            lowest,
+            first_negative,
+            upcoming,
+        }
+    }
+
+    pub fn lowest_point(&self) -> Option<&ForecastPoint> {
+        self.points.get(self.lowest)
+    }
+
+    pub fn final_balance(&self) -> f64 {
+        self.points
+            .last()
+            .map(|p| p.balance)
+            .unwrap_or(0.0)
+    }
+}
+
+// ============================================================================
+// Projection helpers
+// ============================================================================
+
+/// List every occurrence of the active recurring entries between `today` and `end`
+/// (inclusive), sorted by date. Entries still pending for the current month are
+/// assumed to post today if their day has already passed; months already posted
+/// ahead of time (`fitui recurring run --until`) are skipped.
+pub fn scheduled_occurrences(
+    entries: &[RecurringEntry],
+    today: NaiveDate,
+    end: NaiveDate
+) -> Vec<(NaiveDate, RecurringEntry)> {
+    let mut occurrences = Vec::new();
+
+    for entry in entries.iter().filter(|e| e.active) {
+        let (mut year, mut month) = (today.year(), today.month());
+
+        loop {
+            let date = entry.due_date(year, month);
+            if date > end {
+                break;
+            }
+
+            let month_key = format!("{:04}-{:02}", year, month);
+            if entry.last_inserted_month < month_key {
+                occurrences.push((date.max(today), entry.clone()));
+            }
+
+            if month == 12 {
+                year += 1;
+                month = 1;
+            } else {
+                month += 1;
+            }
+        }
+    }
+
+    occurrences.sort_by_key(|(date, _)| *date);
+    occurrences
+}
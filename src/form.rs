@@ -35,6 +35,15 @@ pub struct TransactionForm {
     pub date: String,
     pub recurring: bool,
     pub active: Field,
+
+    // Set by categorisation rules, not edited directly
+    pub notes: String,
+}
+
+impl Default for TransactionForm {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl TransactionForm {
@@ -44,9 +53,10 @@ impl TransactionForm {
             amount: String::new(),
             kind: TransactionType::Debit,
             tag_index: 0,
-            date: "2026-02-11".into(),
+            date: chrono::Local::now().format("%Y-%m-%d").to_string(),
             recurring: false,
             active: Field::Source,
+            notes: String::new(),
         }
     }
 
@@ -0,0 +1,88 @@
+use chrono::{ Local, NaiveDate };
+use serde::Serialize;
+
+use crate::models::{ Goal, Transaction, TransactionType };
+
+/// Average month length used to turn days into months
+const DAYS_PER_MONTH: f64 = 30.44;
+
+/// Months of contribution history used to judge whether a goal is on track
+const RECENT_MONTHS: i64 = 3;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GoalProgress {
+    pub goal: Goal,
+    pub saved: f64,
+    pub remaining: f64,
+    pub months_left: f64,
+    pub required_monthly: f64,
+    pub recent_monthly: f64,
+}
+
+impl GoalProgress {
+    /// Expenses tagged with the goal's tag are money set aside for it; income
+    /// with the same tag counts as a withdrawal.
+    pub fn new(goal: &Goal, transactions: &[Transaction], today: NaiveDate) -> Self {
+        let contributions = transactions.iter().filter(|tx| tx.tag == goal.tag);
+
+        let saved: f64 = contributions
+            .clone()
+            .map(signed_contribution)
+            .sum();
+
+        let recent_since = today - chrono::Duration::days(((RECENT_MONTHS as f64) * DAYS_PER_MONTH) as i64);
+        let recent: f64 = contributions
+            .filter(|tx| {
+                NaiveDate::parse_from_str(&tx.date, "%Y-%m-%d")
+                    .map(|d| d > recent_since && d <= today)
+                    .unwrap_or(false)
+            })
+            .map(signed_contribution)
+            .sum();
+
+        let remaining = (goal.target - saved).max(0.0);
+
+        let months_left = NaiveDate::parse_from_str(&goal.target_date, "%Y-%m-%d")
+            .map(|d| ((d - today).num_days() as f64 / DAYS_PER_MONTH).max(0.0))
+            .unwrap_or(0.0);
+
+        // Once the target date has passed everything left is due now
+        let required_monthly = if months_left >= 1.0 { remaining / months_left } else { remaining };
+
+        Self {
+            goal: goal.clone(),
+            saved,
+            remaining,
+            months_left,
+            required_monthly,
+            recent_monthly: recent / (RECENT_MONTHS as f64),
+        }
+    }
+
+    pub fn ratio(&self) -> f64 {
+        if self.goal.target > 0.0 { (self.saved / self.goal.target).clamp(0.0, 1.0) } else { 1.0 }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.remaining <= 0.0
+    }
+
+    pub fn on_track(&self) -> bool {
+        self.is_complete() || self.recent_monthly >= self.required_monthly
+    }
+}
+
+fn signed_contribution(tx: &Transaction) -> f64 {
+    match tx.kind {
+        TransactionType::Debit => tx.amount,
+        TransactionType::Credit => -tx.amount,
+    }
+}
+
+pub fn calculate_goal_progress(goals: &[Goal], transactions: &[Transaction]) -> Vec<GoalProgress> {
+    let today = Local::now().date_naive();
+    goals
+        .iter()
+        .map(|g| GoalProgress::new(g, transactions, today))
+        .collect()
+}
@@ -2,13 +2,29 @@ use crossterm::event::KeyCode;
 use rusqlite::Connection;
 
 use crate::app::{App, Mode};
-use crate::stats;
+use crate::quickadd;
+use crate::ui::{
+    backup, duplicates, envelopes, export, forecast, goals, import, profile, rules, stats,
+    subscriptions,
+};
 
 pub fn handle_key(app: &mut App, key: KeyCode, conn: &Connection) -> bool {
     match app.mode {
         Mode::Normal => handle_normal(app, key, conn),
         Mode::Adding => handle_form(app, key, conn),
+        Mode::QuickAdd => quickadd::handle_quick_add(app, key, conn),
         Mode::Stats => stats::handle_stats(app, key),
+        Mode::Forecast => forecast::handle_forecast(app, key),
+        Mode::Suggestions => subscriptions::handle_suggestions(app, key, conn),
+        Mode::Envelopes => envelopes::handle_envelopes(app, key, conn),
+        Mode::ConfirmOverBudget => handle_budget_confirm(app, key, conn),
+        Mode::Goals => goals::handle_goals(app, key, conn),
+        Mode::Import => import::handle_import(app, key, conn),
+        Mode::Rules => rules::handle_rules(app, key, conn),
+        Mode::Duplicates => duplicates::handle_duplicates(app, key, conn),
+        Mode::Export => export::handle_export(app, key),
+        Mode::Backup => backup::handle_backup(app, key, conn),
+        Mode::Profiles => profile::handle_profiles(app, key),
     }
 }
 
@@ -24,20 +40,64 @@ fn handle_normal(app: &mut App, key: KeyCode, conn: &Connection) -> bool {
             app.mode = Mode::Adding;
         }
 
+        KeyCode::Char('A') | KeyCode::Char(':') => {
+            app.quick_add.clear();
+            app.mode = Mode::QuickAdd;
+        }
+
         KeyCode::Char('s') => {
-            app.mode = Mode::Stats;
+            app.open_stats();
+        }
+
+        KeyCode::Char('f') => {
+            app.mode = Mode::Forecast;
+        }
+
+        KeyCode::Char('r') => {
+            app.open_suggestions();
+        }
+
+        KeyCode::Char('v') => {
+            app.mode = Mode::Envelopes;
+        }
+
+        KeyCode::Char('g') => {
+            app.mode = Mode::Goals;
+        }
+
+        KeyCode::Char('i') => {
+            app.import = import::ImportStage::new();
+            app.mode = Mode::Import;
+        }
+
+        KeyCode::Char('R') => {
+            app.open_rules();
+        }
+
+        KeyCode::Char('E') => {
+            app.export = export::ExportView::new();
+            app.mode = Mode::Export;
+        }
+
+        KeyCode::Char('B') => {
+            app.backup = backup::BackupView::new();
+            app.mode = Mode::Backup;
+        }
+
+        KeyCode::Char('D') => {
+            app.open_duplicates(conn);
+        }
+
+        KeyCode::Char('P') => {
+            app.open_profiles();
         }
 
-        KeyCode::Up => {
-            if app.selected > 0 {
-                app.selected -= 1;
-            }
+        KeyCode::Up if app.selected > 0 => {
+            app.selected -= 1;
         }
 
-        KeyCode::Down => {
-            if app.selected + 1 < len {
-                app.selected += 1;
-            }
+        KeyCode::Down if app.selected + 1 < len => {
+            app.selected += 1;
         }
 
         KeyCode::Char('d') => {
@@ -91,11 +151,29 @@ fn handle_form(app: &mut App, key: KeyCode, conn: &Connection) -> bool {
         }
 
         KeyCode::Enter => {
+            app.submit_form(conn);
+        }
+
+        _ => {}
+    }
+
+    false
+}
+
+fn handle_budget_confirm(app: &mut App, key: KeyCode, conn: &Connection) -> bool {
+    match key {
+        KeyCode::Char('y') | KeyCode::Enter => {
             app.save_transaction(conn);
             app.form.reset();
             app.mode = Mode::Normal;
         }
 
+        // Back to the form so the entry can be changed
+        KeyCode::Char('n') | KeyCode::Esc => {
+            app.pending_breaches.clear();
+            app.mode = Mode::Adding;
+        }
+
         _ => {}
     }
 
@@ -0,0 +1,207 @@
+use std::{ io::Write, process::{ Command, Stdio } };
+
+use chrono::Local;
+use serde::{ Deserialize, Deserializer, Serialize };
+use serde_json::{ json, Value };
+
+use crate::{ budget::BudgetStatus, models::Transaction, profile };
+
+/// Shell commands run on data events, configured under `hooks` in config.yaml.
+/// Each event takes one command or a list of them.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Hooks {
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub transaction_added: Vec<String>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub transaction_updated: Vec<String>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub transaction_deleted: Vec<String>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub recurring_posted: Vec<String>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub budget_exceeded: Vec<String>,
+}
+
+fn one_or_many<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(command) => vec![command],
+        OneOrMany::Many(commands) => commands,
+    })
+}
+
+/// The result of a write together with the hooks that failed to start; the
+/// write itself went through either way
+#[derive(Debug, Clone)]
+pub struct WithWarnings<T> {
+    pub value: T,
+    pub warnings: Vec<String>,
+}
+
+pub enum Event<'a> {
+    TransactionAdded(&'a Transaction),
+    TransactionUpdated {
+        before: &'a Transaction,
+        after: &'a Transaction,
+    },
+    TransactionDeleted(&'a Transaction),
+    RecurringPosted(&'a [Transaction]),
+    BudgetExceeded {
+        status: &'a BudgetStatus,
+        transaction: &'a Transaction,
+    },
+}
+
+impl Event<'_> {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Event::TransactionAdded(_) => "transaction_added",
+            Event::TransactionUpdated { .. } => "transaction_updated",
+            Event::TransactionDeleted(_) => "transaction_deleted",
+            Event::RecurringPosted(_) => "recurring_posted",
+            Event::BudgetExceeded { .. } => "budget_exceeded",
+        }
+    }
+
+    fn commands<'h>(&self, hooks: &'h Hooks) -> &'h [String] {
+        match self {
+            Event::TransactionAdded(_) => &hooks.transaction_added,
+            Event::TransactionUpdated { .. } => &hooks.transaction_updated,
+            Event::TransactionDeleted(_) => &hooks.transaction_deleted,
+            Event::RecurringPosted(_) => &hooks.recurring_posted,
+            Event::BudgetExceeded { .. } => &hooks.budget_exceeded,
+        }
+    }
+
+    /// JSON written to the hook's stdin
+    pub fn payload(&self) -> Value {
+        let mut payload = json!({
+            "event": self.name(),
+            "profile": profile::active().profile,
+            "timestamp": Local::now().to_rfc3339(),
+        });
+
+        let details = match self {
+            Event::TransactionAdded(tx) | Event::TransactionDeleted(tx) => json!({ "transaction": tx }),
+            Event::TransactionUpdated { before, after } => json!({ "before": before, "transaction": after }),
+            Event::RecurringPosted(transactions) => json!({ "transactions": transactions }),
+            Event::BudgetExceeded { status, transaction } =>
+                json!({
+                    "budget": {
+                        "tag": status.tag,
+                        "limit": status.limit,
+                        "spent": status.spent,
+                        "month": transaction.date.get(..7),
+                    },
+                    "transaction": transaction,
+                }),
+        };
+
+        if let (Some(payload), Value::Object(details)) = (payload.as_object_mut(), details) {
+            payload.extend(details);
+        }
+        payload
+    }
+}
+
+/// Start every command configured for the event with its JSON on stdin.
+/// Hooks run in the background with their output discarded; returns the
+/// commands that couldn't be started.
+pub fn fire(hooks: &Hooks, event: &Event) -> Vec<String> {
+    let commands = event.commands(hooks);
+    if commands.is_empty() {
+        return Vec::new();
+    }
+
+    let payload = event.payload().to_string();
+    let mut errors = Vec::new();
+
+    for command in commands {
+        if let Err(e) = spawn(command, &payload) {
+            errors.push(format!("{} hook '{}' failed: {}", event.name(), command, e));
+        }
+    }
+
+    errors
+}
+
+/// `fire` for several events, e.g. one per imported row
+pub fn fire_all(hooks: &Hooks, events: &[Event]) -> Vec<String> {
+    events
+        .iter()
+        .flat_map(|event| fire(hooks, event))
+        .collect()
+}
+
+fn spawn(command: &str, payload: &str) -> std::io::Result<()> {
+    let (shell, flag) = if cfg!(windows) { ("cmd", "/C") } else { ("sh", "-c") };
+    let mut child = Command::new(shell)
+        .args([flag, command])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    // A hook that ignores stdin and exits early closes the pipe; that's fine
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(payload.as_bytes());
+    }
+
+    // Reap the process without blocking the UI
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+/// Hooks that record each run, for tests of the write paths that fire them
+#[cfg(test)]
+pub(crate) mod recording {
+    use std::{ fs, path::PathBuf, thread, time::{ Duration, Instant } };
+
+    use super::Hooks;
+
+    /// Hooks that append their event's name to a fresh file named after `test`
+    pub fn hooks(test: &str) -> (Hooks, PathBuf) {
+        let path = std::env::temp_dir().join(format!("fitui-hooks-{}-{}.log", test, std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let record = |event: &str| vec![format!("echo {} >> '{}'", event, path.display())];
+        let hooks = Hooks {
+            transaction_added: record("transaction_added"),
+            transaction_updated: record("transaction_updated"),
+            transaction_deleted: record("transaction_deleted"),
+            recurring_posted: record("recurring_posted"),
+            budget_exceeded: record("budget_exceeded"),
+        };
+        (hooks, path)
+    }
+
+    /// The events recorded, sorted, once `expected` hooks have run. Hooks run in
+    /// the background, so this waits for them and then a little longer to catch
+    /// any extra runs.
+    pub fn events(path: &PathBuf, expected: usize) -> Vec<String> {
+        let read = || -> Vec<String> {
+            fs::read_to_string(path)
+                .unwrap_or_default()
+                .lines()
+                .map(String::from)
+                .collect()
+        };
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while read().len() < expected && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(20));
+        }
+        thread::sleep(Duration::from_millis(200));
+
+        let mut events = read();
+        events.sort();
+        let _ = fs::remove_file(path);
+        events
+    }
+}
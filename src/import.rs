@@ -0,0 +1,225 @@
+pub mod csv;
+pub mod journal;
+pub mod ofx;
+
+use std::{ collections::{ BTreeMap, HashSet }, path::PathBuf };
+
+use rusqlite::Connection;
+
+use crate::{
+    db,
+    hooks::{ self, Event, Hooks, WithWarnings },
+    models::{ Tag, Transaction, TransactionType },
+    rules::{ RuleInput, RuleSet },
+};
+
+use self::csv::CsvProfile;
+
+/// A transaction read from an external file, waiting for review
+#[derive(Debug, Clone)]
+pub struct ImportRow {
+    pub date: String,
+    pub source: String,
+    pub amount: f64,
+    pub kind: TransactionType,
+    pub tag: Tag,
+    pub skip: bool,
+    // (account, FITID) for statement formats with stable transaction ids
+    pub external_id: Option<(String, String)>,
+    // Why the row was skipped automatically, shown during review
+    pub note: Option<String>,
+    // Stored with the transaction; usually filled in by a rule
+    pub notes: String,
+}
+
+/// Rows loaded from one file, reviewed before anything touches the database
+pub struct ImportSession {
+    pub path: PathBuf,
+    pub rows: Vec<ImportRow>,
+    pub errors: Vec<String>,
+    pub selected: usize,
+}
+
+impl ImportSession {
+    pub fn selected_count(&self) -> usize {
+        self.rows
+            .iter()
+            .filter(|r| !r.skip)
+            .count()
+    }
+}
+
+// ============================================================================
+// Loading and committing
+// ============================================================================
+
+/// Expand a leading `~` so paths can be typed the way they are in a shell
+pub fn expand_path(input: &str) -> PathBuf {
+    let trimmed = input.trim();
+    if let Some(rest) = trimmed.strip_prefix("~/") && let Some(home) = directories::UserDirs::new() {
+        return home.home_dir().join(rest);
+    }
+    PathBuf::from(trimmed)
+}
+
+/// Read a file into a review session. OFX/QFX statements and hledger journals
+/// are detected by extension; everything else is read as CSV using `profile`.
+pub fn load_file(
+    conn: &Connection,
+    path: PathBuf,
+    profile: Option<&CsvProfile>,
+    ofx_accounts: &BTreeMap<String, String>,
+    rules: &RuleSet,
+    tags: &[Tag]
+) -> Result<ImportSession, String> {
+    let extension = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    let (mut rows, errors) = match (extension.as_str(), profile) {
+        ("ofx" | "qfx", _) => ofx::read_ofx(&path, ofx_accounts, tags)?,
+        ("journal" | "hledger" | "ledger" | "j", _) => journal::read_journal(&path, tags)?,
+        (_, Some(profile)) => csv::read_csv(&path, profile, tags)?,
+        (_, None) => {
+            return Err("No import profiles configured in config.yaml".into());
+        }
+    };
+
+    apply_rules(rules, &mut rows, tags);
+    mark_already_imported(conn, &mut rows).map_err(|e| e.to_string())?;
+
+    Ok(ImportSession {
+        path,
+        rows,
+        errors,
+        selected: 0,
+    })
+}
+
+/// Run the configured rules over freshly read rows
+fn apply_rules(rules: &RuleSet, rows: &mut [ImportRow], tags: &[Tag]) {
+    for row in rows.iter_mut() {
+        let input = RuleInput {
+            source: &row.source,
+            amount: row.amount,
+            kind: row.kind,
+            account: row.external_id.as_ref().map(|(account, _)| account.as_str()),
+        };
+        let Some(outcome) = rules.apply(&input, tags) else {
+            continue;
+        };
+
+        if let Some(tag) = outcome.tag {
+            row.tag = tag;
+        }
+        if let Some(source) = outcome.source {
+            row.source = source;
+        }
+        if let Some(notes) = outcome.notes {
+            row.notes = notes;
+        }
+        if outcome.skip {
+            row.skip = true;
+            row.note = Some(format!("rule {}", outcome.rule));
+        }
+    }
+}
+
+/// Skip rows whose statement id was imported before, or appears twice in the file
+fn mark_already_imported(conn: &Connection, rows: &mut [ImportRow]) -> rusqlite::Result<()> {
+    let mut seen = HashSet::new();
+
+    for row in rows.iter_mut() {
+        let Some((account, fitid)) = &row.external_id else {
+            continue;
+        };
+
+        if db::is_imported(conn, account, fitid)? {
+            row.skip = true;
+            row.note = Some("already imported".into());
+        } else if !seen.insert((account.clone(), fitid.clone())) {
+            row.skip = true;
+            row.note = Some("repeated in file".into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Insert every row not marked as skipped in a single SQLite transaction, then
+/// fire `transaction_added` for each. Returns how many rows were added.
+pub fn commit_rows(conn: &Connection, rows: &[ImportRow], hooks: &Hooks) -> Result<WithWarnings<usize>, String> {
+    let saved = insert_rows(conn, rows).map_err(|e| e.to_string())?;
+
+    let events: Vec<Event> = saved.iter().map(Event::TransactionAdded).collect();
+    let warnings = hooks::fire_all(hooks, &events);
+
+    Ok(WithWarnings { value: saved.len(), warnings })
+}
+
+fn insert_rows(conn: &Connection, rows: &[ImportRow]) -> rusqlite::Result<Vec<Transaction>> {
+    let tx = conn.unchecked_transaction()?;
+    let mut saved = Vec::new();
+
+    for row in rows.iter().filter(|r| !r.skip) {
+        db::add_transaction(&tx, &row.source, row.amount, row.kind, &row.tag, &row.date)?;
+        let id = tx.last_insert_rowid();
+
+        if !row.notes.is_empty() {
+            db::set_transaction_notes(&tx, id, &row.notes)?;
+        }
+
+        if let Some((account, fitid)) = &row.external_id {
+            db::mark_imported(&tx, account, fitid, id)?;
+        }
+
+        saved.push(Transaction {
+            id: id as i32,
+            source: row.source.clone(),
+            amount: row.amount,
+            kind: row.kind,
+            tag: row.tag.clone(),
+            date: row.date.clone(),
+            notes: row.notes.clone(),
+        });
+    }
+
+    tx.commit()?;
+    Ok(saved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hooks::recording;
+    use std::path::Path;
+
+    fn row(source: &str, skip: bool) -> ImportRow {
+        ImportRow {
+            date: "2026-10-01".into(),
+            source: source.into(),
+            amount: 12.5,
+            kind: TransactionType::Debit,
+            tag: Tag::new("food"),
+            skip,
+            external_id: None,
+            note: None,
+            notes: String::new(),
+        }
+    }
+
+    #[test]
+    fn commit_fires_transaction_added_for_each_saved_row() {
+        let conn = db::open_db(Path::new(":memory:")).unwrap();
+        let (hooks, log) = recording::hooks("import");
+
+        let rows = [row("Bakery", false), row("Bakery again", true), row("Cafe", false)];
+        let imported = commit_rows(&conn, &rows, &hooks).unwrap();
+
+        assert_eq!(imported.value, 2);
+        assert!(imported.warnings.is_empty());
+        assert_eq!(recording::events(&log, 2), vec!["transaction_added", "transaction_added"]);
+        assert_eq!(db::get_transactions(&conn).unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,254 @@
+use std::{ fs, path::Path };
+
+use chrono::NaiveDate;
+use serde::{ Deserialize, Serialize };
+
+use crate::models::{ Tag, TransactionType };
+
+use super::ImportRow;
+
+/// Column mapping for one bank/wallet CSV export, configured under
+/// `import_profiles` in config.yaml. Columns are header names, or 1-based
+/// column numbers when the file has no header row.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CsvProfile {
+    #[serde(default = "default_delimiter")]
+    pub delimiter: char,
+    #[serde(default = "default_true")]
+    pub has_header: bool,
+    // Lines to drop before the header (statement preambles)
+    #[serde(default)]
+    pub skip_rows: usize,
+    #[serde(default = "default_encoding")]
+    pub encoding: String,
+
+    pub date_column: String,
+    #[serde(default = "default_date_format")]
+    pub date_format: String,
+
+    pub source_column: String,
+
+    // Either one signed amount column...
+    #[serde(default)]
+    pub amount_column: Option<String>,
+    // ...where negative values are expenses (false for banks that export them positive)
+    #[serde(default = "default_true")]
+    pub negative_is_debit: bool,
+
+    // ...or separate debit/credit columns
+    #[serde(default)]
+    pub debit_column: Option<String>,
+    #[serde(default)]
+    pub credit_column: Option<String>,
+
+    #[serde(default = "default_decimal_separator")]
+    pub decimal_separator: char,
+
+    #[serde(default)]
+    pub tag_column: Option<String>,
+    #[serde(default)]
+    pub default_tag: Option<String>,
+}
+
+fn default_delimiter() -> char {
+    ','
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_encoding() -> String {
+    "utf-8".to_string()
+}
+
+fn default_date_format() -> String {
+    "%Y-%m-%d".to_string()
+}
+
+fn default_decimal_separator() -> char {
+    '.'
+}
+
+impl CsvProfile {
+    /// Mapping for a plain `date,description,amount` export
+    pub fn generic() -> Self {
+        Self {
+            delimiter: default_delimiter(),
+            has_header: true,
+            skip_rows: 0,
+            encoding: default_encoding(),
+            date_column: "date".into(),
+            date_format: default_date_format(),
+            source_column: "description".into(),
+            amount_column: Some("amount".into()),
+            negative_is_debit: true,
+            debit_column: None,
+            credit_column: None,
+            decimal_separator: default_decimal_separator(),
+            tag_column: None,
+            default_tag: None,
+        }
+    }
+}
+
+/// Read a CSV export using `profile`, returning parsed rows and per-line errors
+pub fn read_csv(
+    path: &Path,
+    profile: &CsvProfile,
+    tags: &[Tag]
+) -> Result<(Vec<ImportRow>, Vec<String>), String> {
+    if !profile.delimiter.is_ascii() {
+        return Err(format!("CSV delimiter '{}' must be a single ASCII character", profile.delimiter));
+    }
+
+    let bytes = fs::read(path).map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+
+    let encoding = encoding_rs::Encoding
+        ::for_label(profile.encoding.as_bytes())
+        .ok_or_else(|| format!("Unknown encoding '{}'", profile.encoding))?;
+    let (text, _, _) = encoding.decode(&bytes);
+
+    let body: String = text
+        .lines()
+        .skip(profile.skip_rows)
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut reader = ::csv::ReaderBuilder
+        ::new()
+        .delimiter(profile.delimiter as u8)
+        .has_headers(profile.has_header)
+        .flexible(true)
+        .trim(::csv::Trim::All)
+        .from_reader(body.as_bytes());
+
+    let headers: Vec<String> = if profile.has_header {
+        reader
+            .headers()
+            .map_err(|e| format!("Could not read header row: {}", e))?
+            .iter()
+            .map(|h| h.to_lowercase())
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let column = |name: &str| -> Result<usize, String> {
+        if let Ok(n) = name.parse::<usize>() {
+            return n.checked_sub(1).ok_or_else(|| "Column numbers start at 1".to_string());
+        }
+        headers
+            .iter()
+            .position(|h| *h == name.to_lowercase())
+            .ok_or_else(|| format!("Column '{}' not found in header", name))
+    };
+
+    let date_col = column(&profile.date_column)?;
+    let source_col = column(&profile.source_column)?;
+    let amount_col = profile.amount_column.as_deref().map(&column).transpose()?;
+    let debit_col = profile.debit_column.as_deref().map(&column).transpose()?;
+    let credit_col = profile.credit_column.as_deref().map(&column).transpose()?;
+    let tag_col = profile.tag_column.as_deref().map(&column).transpose()?;
+
+    if amount_col.is_none() && debit_col.is_none() && credit_col.is_none() {
+        return Err("Profile needs amount_column or debit_column/credit_column".into());
+    }
+
+    let default_tag = profile.default_tag
+        .as_deref()
+        .map(Tag::new)
+        .unwrap_or_else(|| Tag::new("other"));
+
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+    let first_line = profile.skip_rows + if profile.has_header { 2 } else { 1 };
+
+    for (i, record) in reader.records().enumerate() {
+        let line = first_line + i;
+        let record = match record {
+            Ok(r) => r,
+            Err(e) => {
+                errors.push(format!("Line {}: {}", line, e));
+                continue;
+            }
+        };
+
+        let field = |col: usize| record.get(col).unwrap_or("").trim();
+
+        let date = match NaiveDate::parse_from_str(field(date_col), &profile.date_format) {
+            Ok(d) => d.format("%Y-%m-%d").to_string(),
+            Err(_) => {
+                errors.push(format!("Line {}: invalid date '{}'", line, field(date_col)));
+                continue;
+            }
+        };
+
+        let signed = if let Some(col) = amount_col {
+            parse_amount(field(col), profile.decimal_separator).map(|v| {
+                if profile.negative_is_debit { v } else { -v }
+            })
+        } else {
+            let debit = debit_col
+                .map(&field)
+                .filter(|v| !v.is_empty())
+                .and_then(|v| parse_amount(v, profile.decimal_separator));
+            let credit = credit_col
+                .map(&field)
+                .filter(|v| !v.is_empty())
+                .and_then(|v| parse_amount(v, profile.decimal_separator));
+
+            match (debit, credit) {
+                (Some(d), _) if d != 0.0 => Some(-d.abs()),
+                (_, Some(c)) => Some(c.abs()),
+                (Some(d), None) => Some(d),
+                (None, None) => None,
+            }
+        };
+
+        let Some(signed) = signed else {
+            errors.push(format!("Line {}: missing or invalid amount", line));
+            continue;
+        };
+
+        let tag = tag_col
+            .map(|c| Tag::new(&field(c).to_lowercase()))
+            .filter(|t| tags.contains(t))
+            .unwrap_or_else(|| default_tag.clone());
+
+        rows.push(ImportRow {
+            date,
+            source: field(source_col).to_string(),
+            amount: signed.abs(),
+            kind: if signed < 0.0 { TransactionType::Debit } else { TransactionType::Credit },
+            tag,
+            skip: false,
+            external_id: None,
+            note: None,
+            notes: String::new(),
+        });
+    }
+
+    Ok((rows, errors))
+}
+
+/// Parse amounts like `1,234.50`, `-649`, `₹ 1.234,50` or `(45.00)`
+pub fn parse_amount(raw: &str, decimal_separator: char) -> Option<f64> {
+    let negative = raw.contains('-') || (raw.starts_with('(') && raw.ends_with(')'));
+
+    let cleaned: String = raw
+        .chars()
+        .filter_map(|c| {
+            if c.is_ascii_digit() {
+                Some(c)
+            } else if c == decimal_separator {
+                Some('.')
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    let value: f64 = cleaned.parse().ok()?;
+    Some(if negative { -value } else { value })
+}
@@ -0,0 +1,256 @@
+use std::{ fs, path::Path };
+
+use chrono::NaiveDate;
+
+use crate::models::{ Tag, TransactionType };
+
+use super::ImportRow;
+
+/// A transaction entry as written in the journal, before it becomes rows
+struct Entry {
+    line: usize,
+    date: String,
+    description: String,
+    notes: Vec<String>,
+    postings: Vec<Posting>,
+}
+
+struct Posting {
+    account: String,
+    amount: Option<f64>,
+}
+
+/// Read an hledger (or ledger) journal. Every posting to an `Expenses:` or
+/// `Income:` account becomes one row, tagged with the last account component;
+/// entries that only move money between asset accounts are shown but skipped.
+/// Directives and periodic/auto transactions are ignored.
+pub fn read_journal(path: &Path, tags: &[Tag]) -> Result<(Vec<ImportRow>, Vec<String>), String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+
+    let mut current: Option<Entry> = None;
+    // Inside a periodic (~), auto (=) or other directive block
+    let mut skipping = false;
+
+    for (index, raw) in text.lines().enumerate() {
+        let line_no = index + 1;
+        let indented = raw.starts_with(' ') || raw.starts_with('\t');
+        let line = raw.trim();
+
+        if indented && !line.is_empty() {
+            if skipping {
+                continue;
+            }
+            if let Some(entry) = current.as_mut() {
+                if let Some(comment) = line.strip_prefix(';').or_else(|| line.strip_prefix('#')) {
+                    entry.notes.push(comment.trim().to_string());
+                } else {
+                    match parse_posting(line) {
+                        Ok((posting, comment)) => {
+                            entry.postings.push(posting);
+                            entry.notes.extend(comment);
+                        }
+                        Err(e) => errors.push(format!("Line {}: {}", line_no, e)),
+                    }
+                }
+            }
+            continue;
+        }
+
+        if let Some(entry) = current.take() {
+            build_rows(entry, tags, &mut rows, &mut errors);
+        }
+        skipping = false;
+
+        if line.is_empty() || line.starts_with([';', '#', '*', '%']) {
+            continue;
+        }
+
+        if line.starts_with(|c: char| c.is_ascii_digit()) {
+            match parse_header(line) {
+                Ok((date, description, comment)) => {
+                    current = Some(Entry {
+                        line: line_no,
+                        date,
+                        description,
+                        notes: comment.into_iter().collect(),
+                        postings: Vec::new(),
+                    });
+                }
+                Err(e) => {
+                    errors.push(format!("Line {}: {}", line_no, e));
+                    skipping = true;
+                }
+            }
+        } else {
+            skipping = true;
+        }
+    }
+
+    if let Some(entry) = current.take() {
+        build_rows(entry, tags, &mut rows, &mut errors);
+    }
+
+    if rows.is_empty() && errors.is_empty() {
+        errors.push("No transactions found in journal".into());
+    }
+
+    Ok((rows, errors))
+}
+
+/// `2026-10-01 * (code) Description ; comment` -> (date, description, comment)
+fn parse_header(line: &str) -> Result<(String, String, Option<String>), String> {
+    let (date_part, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+
+    // Only the primary date of `DATE=DATE2` is used
+    let date_part = date_part.split('=').next().unwrap_or(date_part);
+    let date = ["%Y-%m-%d", "%Y/%m/%d", "%Y.%m.%d"]
+        .iter()
+        .find_map(|fmt| NaiveDate::parse_from_str(date_part, fmt).ok())
+        .ok_or_else(|| format!("unsupported date '{}' (a full year is required)", date_part))?;
+
+    let (description, comment) = split_comment(rest);
+    let mut description = description.trim_start();
+
+    if let Some(rest) = description.strip_prefix(['*', '!']) {
+        description = rest.trim_start();
+    }
+    if description.starts_with('(') && let Some(end) = description.find(')') {
+        description = description[end + 1..].trim_start();
+    }
+
+    Ok((date.format("%Y-%m-%d").to_string(), description.trim().to_string(), comment))
+}
+
+/// `Expenses:food   $250.00  ; comment` -> posting and optional comment
+fn parse_posting(line: &str) -> Result<(Posting, Option<String>), String> {
+    let (body, comment) = split_comment(line);
+
+    // Accounts may contain single spaces; two spaces or a tab start the amount
+    let (account, amount) = match body.find("  ").into_iter().chain(body.find('\t')).min() {
+        Some(pos) => (body[..pos].trim(), body[pos..].trim()),
+        None => (body.trim(), ""),
+    };
+
+    // Virtual postings are written (Account) or [Account]
+    let account = account
+        .trim_start_matches(['(', '['])
+        .trim_end_matches([')', ']'])
+        .to_string();
+
+    // Drop balance assertions and prices
+    let amount = amount.split(['=', '@']).next().unwrap_or("").trim();
+    let amount = if amount.is_empty() {
+        None
+    } else {
+        Some(parse_journal_amount(amount).ok_or_else(|| format!("invalid amount '{}'", amount))?)
+    };
+
+    Ok((Posting { account, amount }, comment))
+}
+
+fn split_comment(s: &str) -> (&str, Option<String>) {
+    match s.split_once(';') {
+        Some((body, comment)) => {
+            let comment = comment.trim();
+            (body, (!comment.is_empty()).then(|| comment.to_string()))
+        }
+        None => (s, None),
+    }
+}
+
+/// Amount with its commodity stripped: `$-1,250.50`, `-250 INR`, `€1.234,50`
+fn parse_journal_amount(raw: &str) -> Option<f64> {
+    let negative = raw.contains('-');
+    let number: String = raw
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == '.' || *c == ',')
+        .collect();
+
+    // Whichever separator comes last is the decimal mark, unless a lone
+    // comma is followed by exactly three digits (a thousands separator)
+    let normalized = match (number.rfind('.'), number.rfind(',')) {
+        (Some(dot), Some(comma)) if comma > dot => number.replace('.', "").replace(',', "."),
+        (Some(_), Some(_)) => number.replace(',', ""),
+        (None, Some(comma)) if number.len() - comma - 1 != 3 => number.replace(',', "."),
+        _ => number.replace(',', ""),
+    };
+
+    let value: f64 = normalized.parse().ok()?;
+    Some(if negative { -value } else { value })
+}
+
+fn build_rows(entry: Entry, tags: &[Tag], rows: &mut Vec<ImportRow>, errors: &mut Vec<String>) {
+    let explicit: f64 = entry.postings
+        .iter()
+        .filter_map(|p| p.amount)
+        .sum();
+    let elided = entry.postings
+        .iter()
+        .filter(|p| p.amount.is_none())
+        .count();
+
+    // hledger's `payee | note` descriptions
+    let (source, mut notes) = match entry.description.split_once('|') {
+        Some((payee, note)) => (payee.trim().to_string(), vec![note.trim().to_string()]),
+        None => (entry.description.clone(), Vec::new()),
+    };
+    notes.extend(entry.notes);
+    let notes = notes.join("; ");
+
+    let row = |amount: f64, tag: Tag| ImportRow {
+        date: entry.date.clone(),
+        source: source.clone(),
+        amount: amount.abs(),
+        // Money flowing into an expense (or out of income) is spending
+        kind: if amount > 0.0 { TransactionType::Debit } else { TransactionType::Credit },
+        tag,
+        skip: false,
+        external_id: None,
+        note: None,
+        notes: notes.clone(),
+    };
+
+    let mut found = false;
+    for posting in entry.postings.iter().filter(|p| is_category(&p.account)) {
+        found = true;
+
+        let amount = match posting.amount {
+            Some(a) => a,
+            // Summing the other postings leaves float noise like 12.299999999999997
+            None if elided == 1 => (-explicit * 100.0).round() / 100.0,
+            None => {
+                errors.push(format!("Line {}: cannot infer amount for {}", entry.line, posting.account));
+                continue;
+            }
+        };
+
+        let tag = posting.account
+            .rsplit(':')
+            .next()
+            .map(|t| Tag::new(&t.to_lowercase()))
+            .filter(|t| tags.contains(t))
+            .unwrap_or_else(|| Tag::new("other"));
+
+        rows.push(row(amount, tag));
+    }
+
+    if !found {
+        let amount = entry.postings
+            .iter()
+            .filter_map(|p| p.amount)
+            .fold(0.0_f64, |max, a| max.max(a.abs()));
+
+        let mut transfer = row(amount, Tag::new("other"));
+        transfer.skip = true;
+        transfer.note = Some("transfer between accounts".into());
+        rows.push(transfer);
+    }
+}
+
+fn is_category(account: &str) -> bool {
+    let root = account.split(':').next().unwrap_or("").to_lowercase();
+    matches!(root.as_str(), "expenses" | "expense" | "income" | "revenue" | "revenues")
+}
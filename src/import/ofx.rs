@@ -0,0 +1,154 @@
+use std::{ collections::BTreeMap, fs, path::Path };
+
+use chrono::NaiveDate;
+
+use crate::models::{ Tag, TransactionType };
+
+use super::ImportRow;
+
+/// Read an OFX/QFX statement. Both the SGML (OFX 1.x) and XML (OFX 2.x)
+/// variants are handled by the same scanner: leaf elements are read up to the
+/// next tag, so missing SGML end tags don't matter.
+pub fn read_ofx(
+    path: &Path,
+    accounts: &BTreeMap<String, String>,
+    tags: &[Tag]
+) -> Result<(Vec<ImportRow>, Vec<String>), String> {
+    let bytes = fs::read(path).map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+    let text = decode(&bytes);
+
+    if !text.to_uppercase().contains("<OFX>") {
+        return Err(format!("{} is not an OFX/QFX file", path.display()));
+    }
+
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+
+    let mut account = String::new();
+    let mut current: Option<BTreeMap<String, String>> = None;
+
+    for (tag, value) in elements(&text) {
+        match tag.as_str() {
+            "ACCTID" => {
+                account = value;
+            }
+            "STMTTRN" => {
+                current = Some(BTreeMap::new());
+            }
+            "/STMTTRN" => {
+                if let Some(fields) = current.take() {
+                    match build_row(&fields, &account, accounts, tags) {
+                        Ok(row) => rows.push(row),
+                        Err(e) => errors.push(e),
+                    }
+                }
+            }
+            _ => {
+                if let Some(fields) = current.as_mut() && !tag.starts_with('/') && !value.is_empty() {
+                    fields.entry(tag).or_insert(value);
+                }
+            }
+        }
+    }
+
+    if rows.is_empty() && errors.is_empty() {
+        errors.push("No transactions found in statement".into());
+    }
+
+    Ok((rows, errors))
+}
+
+fn build_row(
+    fields: &BTreeMap<String, String>,
+    account: &str,
+    accounts: &BTreeMap<String, String>,
+    tags: &[Tag]
+) -> Result<ImportRow, String> {
+    let fitid = fields.get("FITID").cloned().unwrap_or_default();
+    let label = if fitid.is_empty() { "transaction".to_string() } else { format!("FITID {}", fitid) };
+
+    let date = fields
+        .get("DTPOSTED")
+        .and_then(|d| d.get(..8))
+        .and_then(|d| NaiveDate::parse_from_str(d, "%Y%m%d").ok())
+        .ok_or_else(|| format!("{}: missing or invalid DTPOSTED", label))?;
+
+    let amount: f64 = fields
+        .get("TRNAMT")
+        .and_then(|a| a.replace(',', ".").parse().ok())
+        .ok_or_else(|| format!("{}: missing or invalid TRNAMT", label))?;
+
+    let source = ["NAME", "PAYEE", "MEMO"]
+        .iter()
+        .find_map(|k| fields.get(*k))
+        .cloned()
+        .unwrap_or_else(|| "Unknown".into());
+
+    // Statement accounts are mapped to a tag in config.yaml (ofx_accounts)
+    let tag = accounts
+        .get(account)
+        .map(|t| Tag::new(t))
+        .filter(|t| tags.contains(t))
+        .unwrap_or_else(|| Tag::new("other"));
+
+    Ok(ImportRow {
+        date: date.format("%Y-%m-%d").to_string(),
+        source,
+        amount: amount.abs(),
+        kind: if amount < 0.0 { TransactionType::Debit } else { TransactionType::Credit },
+        tag,
+        skip: false,
+        external_id: if fitid.is_empty() { None } else { Some((account.to_string(), fitid)) },
+        note: None,
+        notes: String::new(),
+    })
+}
+
+/// Split the document into (TAG, text) pairs. Tag names are upper-cased and
+/// closing tags keep their leading slash.
+fn elements(text: &str) -> Vec<(String, String)> {
+    let mut out = Vec::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find('<') {
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+
+        let tag = rest[start + 1..start + end].trim().to_uppercase();
+        rest = &rest[start + end + 1..];
+
+        // Skip XML declarations and processing instructions
+        if tag.starts_with('?') || tag.starts_with('!') {
+            continue;
+        }
+
+        let value_end = rest.find('<').unwrap_or(rest.len());
+        let value = unescape(rest[..value_end].trim());
+
+        out.push((tag, value));
+    }
+
+    out
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// SGML files declare their charset in the plain-text header (CHARSET:1252)
+fn decode(bytes: &[u8]) -> String {
+    let head = String::from_utf8_lossy(&bytes[..bytes.len().min(512)]).to_uppercase();
+
+    if head.contains("CHARSET:1252") || head.contains("ENCODING=\"WINDOWS-1252\"") {
+        let (text, _, _) = encoding_rs::WINDOWS_1252.decode(bytes);
+        return text.into_owned();
+    }
+
+    String::from_utf8_lossy(bytes).into_owned()
+}
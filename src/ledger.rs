@@ -0,0 +1,239 @@
+use std::path::{ Path, PathBuf };
+
+use chrono::{ Local, NaiveDate };
+use rusqlite::Connection;
+
+use crate::{
+    backup::{ self, Backup, RestoreMode, RestoreSummary },
+    budget::Budgets,
+    config::{ read_config, Config },
+    db,
+    duplicates,
+    entry::{ self, AddedEntry, NewEntry, UpdatedEntry },
+    export::{ self, ExportContext, ExportFormat },
+    hooks::{ self, Event, WithWarnings },
+    import::{ self, ImportRow, ImportSession },
+    models::{ Goal, RecurringEntry, Tag, Transaction, TransactionType },
+    profile,
+    query::{ group_transactions, Group, GroupKey, Query },
+    rules::RuleSet,
+    stats::StatsSnapshot,
+};
+
+/// An open fitui database together with the config it is used with. Writes go
+/// through the same validation, budget checks and hooks as the TUI and CLI.
+pub struct Ledger {
+    conn: Connection,
+    config: Config,
+}
+
+impl Ledger {
+    /// Open (and create if needed) the database at `db`
+    pub fn open(db: impl AsRef<Path>, config: Config) -> Result<Self, String> {
+        let conn = db::open_db(db.as_ref()).map_err(|e| e.to_string())?;
+        Ok(Self { conn, config })
+    }
+
+    /// Open a profile's database and config.yaml from the standard locations;
+    /// `None` is the default profile. A missing config.yaml means defaults.
+    pub fn open_profile(name: Option<&str>) -> Result<Self, String> {
+        let paths = profile::resolve(name, None, None)?;
+        let config = if paths.config.exists() { read_config(&paths.config)? } else { Config::default() };
+        Self::open(&paths.db, config)
+    }
+
+    /// A throwaway ledger that lives in memory, handy for tests and previews
+    pub fn in_memory(config: Config) -> Result<Self, String> {
+        Self::open(":memory:", config)
+    }
+
+    /// The underlying SQLite connection, for anything the API doesn't cover.
+    /// Schema changes between versions are not considered breaking.
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn tags(&self) -> Vec<Tag> {
+        self.config.tags
+            .iter()
+            .map(|t| Tag::new(t))
+            .collect()
+    }
+
+    pub fn budgets(&self) -> Budgets {
+        Budgets::from_config(&self.config)
+    }
+
+    // ========================================================================
+    // Transactions
+    // ========================================================================
+
+    /// Every transaction, newest first
+    pub fn transactions(&self) -> Result<Vec<Transaction>, String> {
+        db::get_transactions(&self.conn).map_err(|e| e.to_string())
+    }
+
+    pub fn transaction(&self, id: i32) -> Result<Option<Transaction>, String> {
+        Ok(self.transactions()?.into_iter().find(|tx| tx.id == id))
+    }
+
+    /// Validate and save a transaction: configured tags, rules (when
+    /// `apply_rules_to_manual` is on), budget alerts and duplicate detection
+    pub fn add(&self, entry: NewEntry) -> Result<AddedEntry, String> {
+        entry::add_entry(&self.conn, &self.config, entry)
+    }
+
+    /// Overwrite transaction `id`, with the same validation and budget checks
+    /// as [`Ledger::add`]
+    pub fn update(&self, id: i32, entry: NewEntry) -> Result<UpdatedEntry, String> {
+        entry::update_entry(&self.conn, &self.config, id, entry)
+    }
+
+    /// Delete a transaction, returning it
+    pub fn delete(&self, id: i32) -> Result<WithWarnings<Transaction>, String> {
+        let tx = self.transaction(id)?.ok_or_else(|| format!("no transaction with id {}", id))?;
+        db::delete_transaction(&self.conn, id).map_err(|e| e.to_string())?;
+
+        let warnings = hooks::fire(&self.config.hooks, &Event::TransactionDeleted(&tx));
+        Ok(WithWarnings { value: tx, warnings })
+    }
+
+    // ========================================================================
+    // Queries and stats
+    // ========================================================================
+
+    /// Transactions matching a filter expression such as `tag:food amount>100
+    /// date:2026-10`, oldest first. See [`Query`] for the syntax.
+    pub fn query(&self, expression: &str) -> Result<Vec<Transaction>, String> {
+        let query = Query::parse(expression)?;
+        let mut transactions = query.apply(&self.transactions()?);
+        transactions.sort_by(|a, b| a.date.cmp(&b.date).then(a.id.cmp(&b.id)));
+        Ok(transactions)
+    }
+
+    /// Totals of the transactions matching `expression`, grouped by `keys`
+    pub fn group(&self, expression: &str, keys: &[GroupKey]) -> Result<Vec<Group>, String> {
+        Ok(group_transactions(&self.query(expression)?, keys))
+    }
+
+    /// Totals, spending per tag, monthly history, budgets and goals for `transactions`
+    pub fn stats(&self, transactions: &[Transaction]) -> Result<StatsSnapshot, String> {
+        Ok(StatsSnapshot::new(transactions, &self.budgets(), &self.goals()?))
+    }
+
+    pub fn goals(&self) -> Result<Vec<Goal>, String> {
+        db::get_goals(&self.conn).map_err(|e| e.to_string())
+    }
+
+    // ========================================================================
+    // Recurring entries
+    // ========================================================================
+
+    pub fn recurring(&self) -> Result<Vec<RecurringEntry>, String> {
+        db::get_recurring_entries(&self.conn).map_err(|e| e.to_string())
+    }
+
+    /// Add a monthly entry falling due on `day`; a day that has already passed
+    /// this month is first posted next month
+    pub fn add_recurring(
+        &self,
+        source: &str,
+        amount: f64,
+        kind: TransactionType,
+        tag: &Tag,
+        day: u32
+    ) -> Result<RecurringEntry, String> {
+        entry::add_recurring(&self.conn, &self.config, source, amount, kind, tag.as_str(), day)
+    }
+
+    pub fn delete_recurring(&self, id: i32) -> Result<(), String> {
+        db::delete_recurring_entry(&self.conn, id).map_err(|e| e.to_string())
+    }
+
+    /// Occurrences that would be posted up to `until`, without posting them
+    pub fn pending_recurring(&self, until: NaiveDate) -> Result<Vec<(RecurringEntry, NaiveDate)>, String> {
+        db::pending_recurring(&self.conn, Local::now().date_naive(), until).map_err(|e| e.to_string())
+    }
+
+    /// Post recurring entries that have fallen due up to `until`
+    pub fn post_recurring(&self, until: NaiveDate) -> Result<WithWarnings<Vec<Transaction>>, String> {
+        let posted = db::insert_recurring_until(&self.conn, Local::now().date_naive(), until).map_err(|e|
+            e.to_string()
+        )?;
+
+        let warnings = if posted.is_empty() {
+            Vec::new()
+        } else {
+            hooks::fire(&self.config.hooks, &Event::RecurringPosted(&posted))
+        };
+        Ok(WithWarnings { value: posted, warnings })
+    }
+
+    // ========================================================================
+    // Import, export and backups
+    // ========================================================================
+
+    /// Read a CSV, OFX/QFX or hledger file into rows for review. `csv_profile`
+    /// names one of the config's import profiles; the first is used otherwise.
+    /// Nothing is saved until [`Ledger::commit_import`].
+    pub fn import_file(&self, path: impl Into<PathBuf>, csv_profile: Option<&str>) -> Result<ImportSession, String> {
+        let profile = match csv_profile {
+            Some(name) =>
+                Some(
+                    self.config.import_profiles
+                        .get(name)
+                        .ok_or_else(|| format!("no import profile '{}' in config.yaml", name))?
+                ),
+            None => self.config.import_profiles.values().next(),
+        };
+        let (rules, _) = RuleSet::new(&self.config.rules);
+
+        let mut session = import::load_file(
+            &self.conn,
+            path.into(),
+            profile,
+            &self.config.ofx_accounts,
+            &rules,
+            &self.tags()
+        )?;
+        duplicates::mark_import_duplicates(&mut session.rows, &self.transactions()?, &self.config.duplicates);
+
+        Ok(session)
+    }
+
+    /// Save every row not marked as skipped, returning how many were added
+    pub fn commit_import(&self, rows: &[ImportRow]) -> Result<WithWarnings<usize>, String> {
+        import::commit_rows(&self.conn, rows, &self.config.hooks)
+    }
+
+    /// Render `transactions` as CSV (using the config's export options), an
+    /// HTML report or a ledger/hledger/beancount journal
+    pub fn export(&self, format: ExportFormat, transactions: &[Transaction]) -> Result<String, String> {
+        let recurring = self.recurring()?;
+        let budgets = self.budgets();
+        let context = ExportContext {
+            recurring: &recurring,
+            currency: &self.config.currency,
+            csv: &self.config.csv_export,
+            budgets: &budgets,
+        };
+
+        export::render(format, transactions, &context)
+    }
+
+    /// Everything in the database, plus tags and currency, as a checksummed snapshot
+    pub fn backup(&self) -> Result<Backup, String> {
+        backup::create_backup(&self.conn, &self.config).map_err(|e| e.to_string())
+    }
+
+    /// Load a backup read with [`backup::read_backup`]. Its tags and currency
+    /// are merged into this ledger's config in memory only. Hook failures are
+    /// in the summary's `warnings`.
+    pub fn restore(&mut self, backup: &Backup, mode: RestoreMode) -> Result<RestoreSummary, String> {
+        backup::restore(&self.conn, backup, mode, &mut self.config)
+    }
+}
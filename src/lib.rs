@@ -0,0 +1,84 @@
+//! FiTui's ledger as a library: the same database, validation, budgets, queries,
+//! stats and import/export the `fitui` binary uses, for tools that would rather
+//! link against it than read the SQLite file directly.
+//!
+//! ```no_run
+//! use fitui::{ Ledger, NewEntry, TransactionType };
+//!
+//! let ledger = Ledger::open_profile(None)?;
+//!
+//! ledger.add(NewEntry {
+//!     source: "Coffee".into(),
+//!     amount: 3.5,
+//!     kind: TransactionType::Debit,
+//!     tag: "food".into(),
+//!     date: None,
+//!     notes: String::new(),
+//!     force: false,
+//! })?;
+//!
+//! let october = ledger.query("tag:food date:2026-10")?;
+//! let stats = ledger.stats(&october)?;
+//! println!("{} transactions, {:.2} spent", stats.tx_count, stats.spent);
+//! # Ok::<(), String>(())
+//! ```
+//!
+//! [`Ledger`] is the stable entry point. The modules below it are public for
+//! tools that need more control; the ones hidden from the docs drive the
+//! terminal UI and may change with any release.
+//!
+//! The terminal UI is the default `tui` feature. Depend on the crate with
+//! `default-features = false` to leave it (and ratatui/crossterm) out, adding
+//! the `reports` feature for Rhai report scripts.
+
+pub mod backup;
+pub mod budget;
+pub mod config;
+pub mod db;
+pub mod duplicates;
+pub mod entry;
+pub mod envelopes;
+pub mod export;
+pub mod filter;
+pub mod forecast;
+pub mod goals;
+pub mod hooks;
+pub mod import;
+pub mod ledger;
+pub mod models;
+pub mod profile;
+pub mod query;
+#[cfg(feature = "reports")]
+pub mod reports;
+pub mod rules;
+pub mod stats;
+pub mod subscriptions;
+
+// Terminal UI, used by the fitui binary
+#[cfg(feature = "tui")]
+#[doc(hidden)]
+pub mod app;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
+pub mod form;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
+pub mod handlers;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
+pub mod quickadd;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
+pub mod theme;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
+pub mod ui;
+
+pub use config::Config;
+pub use entry::{ AddedEntry, NewEntry, UpdatedEntry };
+pub use export::ExportFormat;
+pub use hooks::WithWarnings;
+pub use ledger::Ledger;
+pub use models::{ Goal, RecurringEntry, Tag, Transaction, TransactionType };
+pub use query::{ Group, GroupKey, Query };
+pub use stats::StatsSnapshot;
@@ -1,12 +1,5 @@
-mod app;
-mod db;
-mod form;
-mod handlers;
-mod models;
-mod stats;
-mod theme;
-mod ui;
-mod config;
+mod cli;
+mod server;
 
 use std::io;
 
@@ -19,18 +12,45 @@ use crossterm::{
     },
 };
 
+use clap::Parser;
 use ratatui::prelude::*;
-use chrono::Datelike;
 
-use app::App;
+use fitui::{
+    app::{self, App},
+    db, handlers, profile, stats, ui,
+};
 
 fn main() -> io::Result<()> {
-    let conn = db::init_db().unwrap();
+    let cli = cli::Cli::parse();
+
+    match profile::resolve(cli.profile.as_deref(), cli.db, cli.config) {
+        Ok(paths) => profile::activate(paths),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    }
+
+    let mut conn = match db::init_db() {
+        Ok(conn) => conn,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    };
 
-    // Insert recurring entries for the current month on startup
-    let now = chrono::Local::now();
-    let current_month = format!("{:04}-{:02}", now.year(), now.month());
-    db::insert_recurring_for_month(&conn, &current_month).unwrap();
+    // Subcommands run without the TUI
+    if let Some(command) = cli.command {
+        if let Err(e) = cli::run(command, &conn) {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    // Insert recurring entries that fell due this month on startup
+    let today = chrono::Local::now().date_naive();
+    let posted = db::insert_recurring_for_month(&conn, today).unwrap();
 
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -40,23 +60,30 @@ fn main() -> io::Result<()> {
     let mut terminal = Terminal::new(backend)?;
 
     let mut app = App::new(&conn);
+    app.report_recurring(&posted);
 
     loop {
-        let snapshot = stats::StatsSnapshot::new(&app.transactions);
+        // Picks up date changes while the app stays open
+        app.tick(&conn);
+
+        let snapshot = stats::StatsSnapshot::new(&app.transactions, &app.budgets, &app.goals);
 
         terminal.draw(|f| {
             ui::draw_ui(f, &app, &snapshot);
         })?;
 
-        if event::poll(std::time::Duration::from_millis(200))? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
-                    let quit = handlers::handle_key(&mut app, key.code, &conn);
+        if event::poll(std::time::Duration::from_millis(200))?
+            && let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            let quit = handlers::handle_key(&mut app, key.code, &conn);
+
+            if quit {
+                break;
+            }
 
-                    if quit {
-                        break;
-                    }
-                }
+            if let Some(name) = app.switch_profile.take() {
+                switch_profile(&mut app, &mut conn, &name);
             }
         }
     }
@@ -66,3 +93,42 @@ fn main() -> io::Result<()> {
 
     Ok(())
 }
+
+/// Reopen the app on another profile's database and config. Switching drops any
+/// `--db`/`--config` overrides given at startup.
+fn switch_profile(app: &mut App, conn: &mut rusqlite::Connection, name: &str) {
+    let paths = match profile::resolve(Some(name), None, None) {
+        Ok(paths) => paths,
+        Err(e) => {
+            app.notify(e, app::NotificationLevel::Warning);
+            return;
+        }
+    };
+
+    // App::new loads the profile's config, which needs its directory
+    if let Err(e) = profile::ensure_parent(&paths.config) {
+        app.notify(format!("Could not open profile {}: {}", name, e), app::NotificationLevel::Warning);
+        return;
+    }
+
+    let previous = profile::active();
+    profile::activate(paths);
+
+    match db::init_db() {
+        Ok(new_conn) => {
+            *conn = new_conn;
+
+            let today = chrono::Local::now().date_naive();
+            let posted = db::insert_recurring_for_month(conn, today).unwrap_or_default();
+
+            *app = App::new(conn);
+            // Recurring posts take the notification slot; the title names the profile either way
+            app.notify(format!("👤 Switched to profile {}", name), app::NotificationLevel::Info);
+            app.report_recurring(&posted);
+        }
+        Err(e) => {
+            profile::activate(previous);
+            app.notify(format!("Could not open profile {}: {}", name, e), app::NotificationLevel::Warning);
+        }
+    }
+}
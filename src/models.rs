@@ -1,4 +1,8 @@
-#[derive(Debug, Clone, Copy, PartialEq)]
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum TransactionType {
     Credit,
     Debit,
@@ -12,7 +16,8 @@ impl TransactionType {
         }
     }
 
-    pub fn from_str(s: &str) -> Self {
+    /// Anything other than "credit" reads as a debit
+    pub fn parse_lossy(s: &str) -> Self {
         match s {
             "credit" => TransactionType::Credit,
             _ => TransactionType::Debit,
@@ -20,7 +25,8 @@ impl TransactionType {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct Tag(pub String);
 
 impl Tag {
@@ -28,12 +34,12 @@ impl Tag {
         &self.0
     }
 
-    pub fn from_str(s: &str) -> Self {
+    pub fn new(s: &str) -> Self {
         Tag(s.to_string())
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Transaction {
     pub id: i32,
     pub source: String,
@@ -41,8 +47,10 @@ pub struct Transaction {
     pub kind: TransactionType,
     pub tag: Tag,
     pub date: String,
+    #[serde(default)]
+    pub notes: String,
 }
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecurringEntry {
     pub id: i32,
     pub source: String,
@@ -50,5 +58,39 @@ pub struct RecurringEntry {
     pub kind: TransactionType,
     pub tag: Tag,
     pub last_inserted_month: String, // Format: "YYYY-MM"
+    pub day: u32,                    // Day of month the entry falls due
     pub active: bool,
-}
\ No newline at end of file
+}
+
+impl RecurringEntry {
+    /// Date this entry falls due in the given month, clamped for short months
+    pub fn due_date(&self, year: i32, month: u32) -> NaiveDate {
+        (1..=self.day.max(1))
+            .rev()
+            .find_map(|d| NaiveDate::from_ymd_opt(year, month, d))
+            .expect("every month has a first day")
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Envelope {
+    pub id: i32,
+    pub name: String,
+    pub tag: Tag, // Debits with this tag are spent from the envelope
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvelopeAssignment {
+    pub envelope_id: i32,
+    pub month: String, // Format: "YYYY-MM"
+    pub amount: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Goal {
+    pub id: i32,
+    pub name: String,
+    pub target: f64,
+    pub target_date: String, // Format: "YYYY-MM-DD"
+    pub tag: Tag,            // Transactions with this tag count as contributions
+}
@@ -0,0 +1,85 @@
+use std::{ fs, path::PathBuf, sync::RwLock };
+
+use directories::ProjectDirs;
+
+/// Profile used when none is given; keeps the original file locations
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Where the current ledger's database and config live
+#[derive(Debug, Clone)]
+pub struct Paths {
+    pub profile: String,
+    pub db: PathBuf,
+    pub config: PathBuf,
+}
+
+// Set once at startup and again when switching profiles from the TUI
+static ACTIVE: RwLock<Option<Paths>> = RwLock::new(None);
+
+fn project_dirs() -> ProjectDirs {
+    ProjectDirs::from("com", "ayan", "fitui").expect("Could not determine data directory")
+}
+
+/// Paths for a named profile. `db` and `config` (from `--db`/`FITUI_DB` and
+/// `--config`/`FITUI_CONFIG`) replace the profile's own files.
+pub fn resolve(profile: Option<&str>, db: Option<PathBuf>, config: Option<PathBuf>) -> Result<Paths, String> {
+    let profile = profile.unwrap_or(DEFAULT_PROFILE);
+    if profile.is_empty() || !profile.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(format!("invalid profile name '{}' (use letters, digits, - and _)", profile));
+    }
+
+    let dirs = project_dirs();
+    let (data_dir, config_dir) = if profile == DEFAULT_PROFILE {
+        (dirs.data_dir().to_path_buf(), dirs.config_dir().to_path_buf())
+    } else {
+        (dirs.data_dir().join("profiles").join(profile), dirs.config_dir().join("profiles").join(profile))
+    };
+
+    Ok(Paths {
+        profile: profile.to_string(),
+        db: db.unwrap_or_else(|| data_dir.join("budget.db")),
+        config: config.unwrap_or_else(|| config_dir.join("config.yaml")),
+    })
+}
+
+pub fn activate(paths: Paths) {
+    *ACTIVE.write().expect("profile lock poisoned") = Some(paths);
+}
+
+/// Currently active paths, falling back to the default profile
+pub fn active() -> Paths {
+    if let Some(paths) = ACTIVE.read().expect("profile lock poisoned").as_ref() {
+        return paths.clone();
+    }
+
+    resolve(None, None, None).expect("default profile name is valid")
+}
+
+/// Create the parent directory of a profile file if needed
+pub fn ensure_parent(path: &std::path::Path) -> Result<(), String> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).map_err(|e| format!("Could not create {}: {}", parent.display(), e))?;
+    }
+    Ok(())
+}
+
+/// The default profile plus every profile that has a database or config
+pub fn list_profiles() -> Vec<String> {
+    let dirs = project_dirs();
+    let mut profiles = vec![DEFAULT_PROFILE.to_string()];
+
+    for root in [dirs.data_dir().join("profiles"), dirs.config_dir().join("profiles")] {
+        let Ok(entries) = fs::read_dir(root) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if entry.path().is_dir() && let Some(name) = entry.file_name().to_str().filter(|n| *n != DEFAULT_PROFILE) {
+                profiles.push(name.to_string());
+            }
+        }
+    }
+
+    profiles[1..].sort();
+    profiles.dedup();
+    profiles
+}
@@ -0,0 +1,481 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use regex::Regex;
+use serde::Serialize;
+
+use crate::{ filter::parse_date_bound, models::{ Tag, Transaction, TransactionType } };
+
+/// A parsed filter expression. Terms are separated by spaces and must all
+/// match; `!` in front of a term negates it as a whole, so `!date:2026-02..2026-06`
+/// is everything outside that range.
+///
+/// ```text
+/// tag:food,travel  type:debit  amount>=100  date:2026-01..2026-03
+/// date>2026-10  source:amazon*  "source:/^uber (eats)?$/"  notes:refund  coffee
+/// ```
+#[derive(Debug)]
+pub struct Query {
+    // One entry per token: whether it is negated, and the terms it expands to
+    terms: Vec<(bool, Vec<Term>)>,
+}
+
+#[derive(Debug)]
+enum Term {
+    Tags(Vec<Tag>),
+    Kind(TransactionType),
+    Amount(Comparison, f64),
+    DateFrom(NaiveDate),
+    DateTo(NaiveDate),
+    Source(Regex),
+    Notes(Regex),
+    Text(String),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Comparison {
+    Less,
+    LessEqual,
+    Equal,
+    GreaterEqual,
+    Greater,
+}
+
+impl Comparison {
+    fn holds(self, left: f64, right: f64) -> bool {
+        match self {
+            Comparison::Less => left < right,
+            Comparison::LessEqual => left <= right,
+            Comparison::Equal => (left - right).abs() < 0.005,
+            Comparison::GreaterEqual => left >= right,
+            Comparison::Greater => left > right,
+        }
+    }
+}
+
+impl Query {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut terms = Vec::new();
+
+        for token in tokenize(input)? {
+            let (negated, token) = match token.strip_prefix('!') {
+                Some(rest) => (true, rest.to_string()),
+                None => (false, token),
+            };
+            if token.is_empty() {
+                return Err("'!' must be followed by a term".into());
+            }
+
+            terms.push((negated, parse_term(&token)?));
+        }
+
+        Ok(Self { terms })
+    }
+
+    pub fn matches(&self, tx: &Transaction) -> bool {
+        self.terms.iter().all(|(negated, terms)| terms.iter().all(|term| term_matches(term, tx)) != *negated)
+    }
+
+    pub fn apply(&self, transactions: &[Transaction]) -> Vec<Transaction> {
+        transactions
+            .iter()
+            .filter(|tx| self.matches(tx))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Split on whitespace, keeping double-quoted runs together
+fn tokenize(input: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+
+    for c in input.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+
+    if quoted {
+        return Err("unterminated quote in query".into());
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+
+    Ok(tokens)
+}
+
+/// One token can expand to several terms (`date:A..B` is a lower and an upper bound)
+fn parse_term(token: &str) -> Result<Vec<Term>, String> {
+    if let Some(tag) = token.strip_prefix('#') {
+        return Ok(vec![Term::Tags(vec![Tag::new(tag)])]);
+    }
+
+    let Some(split) = token.find([':', '<', '>', '=']) else {
+        return Ok(vec![Term::Text(token.to_lowercase())]);
+    };
+    let (field, rest) = token.split_at(split);
+    let (op, value) = split_operator(rest);
+    if value.is_empty() {
+        return Err(format!("missing value in '{}'", token));
+    }
+
+    match (field.to_lowercase().as_str(), op) {
+        ("tag", ":") => {
+            let tags = value
+                .split(',')
+                .filter(|t| !t.is_empty())
+                .map(|t| Tag::new(t.trim_start_matches('#')))
+                .collect();
+            Ok(vec![Term::Tags(tags)])
+        }
+        ("type", ":") =>
+            match value.to_lowercase().as_str() {
+                "debit" | "expense" => Ok(vec![Term::Kind(TransactionType::Debit)]),
+                "credit" | "income" => Ok(vec![Term::Kind(TransactionType::Credit)]),
+                _ => Err(format!("unknown type '{}' (use debit or credit)", value)),
+            }
+        ("amount", ":") => {
+            match value.split_once("..") {
+                Some((low, high)) => {
+                    let mut terms = Vec::new();
+                    if !low.is_empty() {
+                        terms.push(Term::Amount(Comparison::GreaterEqual, parse_amount(low)?));
+                    }
+                    if !high.is_empty() {
+                        terms.push(Term::Amount(Comparison::LessEqual, parse_amount(high)?));
+                    }
+                    Ok(terms)
+                }
+                None => Ok(vec![Term::Amount(Comparison::Equal, parse_amount(value)?)]),
+            }
+        }
+        ("amount", op) => Ok(vec![Term::Amount(parse_comparison(op, token)?, parse_amount(value)?)]),
+        ("date", ":") => {
+            let (from, to) = value.split_once("..").unwrap_or((value, value));
+            let mut terms = Vec::new();
+            if !from.is_empty() {
+                terms.push(Term::DateFrom(parse_date_bound(from, false)?));
+            }
+            if !to.is_empty() {
+                terms.push(Term::DateTo(parse_date_bound(to, true)?));
+            }
+            Ok(terms)
+        }
+        ("date", op) => {
+            // A month or year compares as a whole: date>2026-10 starts in November
+            let term = match parse_comparison(op, token)? {
+                Comparison::Less => Term::DateTo(before(parse_date_bound(value, false)?)?),
+                Comparison::LessEqual => Term::DateTo(parse_date_bound(value, true)?),
+                Comparison::Equal => {
+                    return Ok(
+                        vec![
+                            Term::DateFrom(parse_date_bound(value, false)?),
+                            Term::DateTo(parse_date_bound(value, true)?)
+                        ]
+                    );
+                }
+                Comparison::GreaterEqual => Term::DateFrom(parse_date_bound(value, false)?),
+                Comparison::Greater => Term::DateFrom(after(parse_date_bound(value, true)?)?),
+            };
+            Ok(vec![term])
+        }
+        ("source", ":") => Ok(vec![Term::Source(parse_pattern(value)?)]),
+        ("notes", ":") => Ok(vec![Term::Notes(parse_pattern(value)?)]),
+        (field, _) =>
+            Err(format!("unknown query field '{}' (use tag, type, amount, date, source or notes)", field)),
+    }
+}
+
+fn split_operator(rest: &str) -> (&str, &str) {
+    for op in [">=", "<=", ":", "<", ">", "="] {
+        if let Some(value) = rest.strip_prefix(op) {
+            return (op, value);
+        }
+    }
+    (rest, "")
+}
+
+fn parse_comparison(op: &str, token: &str) -> Result<Comparison, String> {
+    match op {
+        "<" => Ok(Comparison::Less),
+        "<=" => Ok(Comparison::LessEqual),
+        "=" => Ok(Comparison::Equal),
+        ">=" => Ok(Comparison::GreaterEqual),
+        ">" => Ok(Comparison::Greater),
+        _ => Err(format!("invalid comparison in '{}'", token)),
+    }
+}
+
+fn parse_amount(value: &str) -> Result<f64, String> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite())
+        .ok_or_else(|| format!("invalid amount '{}'", value))
+}
+
+fn before(date: NaiveDate) -> Result<NaiveDate, String> {
+    date.pred_opt().ok_or_else(|| format!("date out of range: {}", date))
+}
+
+fn after(date: NaiveDate) -> Result<NaiveDate, String> {
+    date.succ_opt().ok_or_else(|| format!("date out of range: {}", date))
+}
+
+/// `/regex/`, a glob with `*` and `?` matching the whole text, or a plain substring.
+/// All patterns are case-insensitive.
+fn parse_pattern(value: &str) -> Result<Regex, String> {
+    let pattern = if value.len() > 1 && value.starts_with('/') && value.ends_with('/') {
+        value[1..value.len() - 1].to_string()
+    } else if value.contains(['*', '?']) {
+        let mut pattern = String::from("^");
+        for c in value.chars() {
+            match c {
+                '*' => pattern.push_str(".*"),
+                '?' => pattern.push('.'),
+                c => pattern.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+        pattern.push('$');
+        pattern
+    } else {
+        regex::escape(value)
+    };
+
+    Regex::new(&format!("(?i){}", pattern)).map_err(|e| format!("invalid pattern '{}': {}", value, e))
+}
+
+fn term_matches(term: &Term, tx: &Transaction) -> bool {
+    match term {
+        Term::Tags(tags) => tags.contains(&tx.tag),
+        Term::Kind(kind) => tx.kind == *kind,
+        Term::Amount(op, value) => op.holds(tx.amount, *value),
+        Term::DateFrom(from) => transaction_date(tx).is_some_and(|d| d >= *from),
+        Term::DateTo(to) => transaction_date(tx).is_some_and(|d| d <= *to),
+        Term::Source(pattern) => pattern.is_match(&tx.source),
+        Term::Notes(pattern) => pattern.is_match(&tx.notes),
+        Term::Text(text) =>
+            tx.source.to_lowercase().contains(text) || tx.notes.to_lowercase().contains(text),
+    }
+}
+
+fn transaction_date(tx: &Transaction) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(&tx.date, "%Y-%m-%d").ok()
+}
+
+// ============================================================================
+// Aggregation
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GroupKey {
+    Tag,
+    Month,
+    Year,
+    Type,
+    Source,
+}
+
+impl GroupKey {
+    pub const ALL: [GroupKey; 5] = [GroupKey::Tag, GroupKey::Month, GroupKey::Year, GroupKey::Type, GroupKey::Source];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GroupKey::Tag => "tag",
+            GroupKey::Month => "month",
+            GroupKey::Year => "year",
+            GroupKey::Type => "type",
+            GroupKey::Source => "source",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        GroupKey::ALL.into_iter().find(|k| k.name() == name)
+    }
+
+    fn value(&self, tx: &Transaction) -> String {
+        match self {
+            GroupKey::Tag => tx.tag.as_str().to_string(),
+            GroupKey::Month => tx.date.get(..7).unwrap_or(&tx.date).to_string(),
+            GroupKey::Year => tx.date.get(..4).unwrap_or(&tx.date).to_string(),
+            GroupKey::Type => tx.kind.as_str().to_string(),
+            GroupKey::Source => tx.source.clone(),
+        }
+    }
+}
+
+/// Totals for one combination of group key values
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Group {
+    pub keys: Vec<String>,
+    pub count: usize,
+    pub earned: f64,
+    pub spent: f64,
+    pub net: f64,
+}
+
+/// Sum transactions per distinct key combination, sorted by key. With no keys
+/// everything falls into a single group.
+pub fn group_transactions(transactions: &[Transaction], keys: &[GroupKey]) -> Vec<Group> {
+    let mut groups: BTreeMap<Vec<String>, Group> = BTreeMap::new();
+
+    for tx in transactions {
+        let values: Vec<String> = keys
+            .iter()
+            .map(|k| k.value(tx))
+            .collect();
+        let group = groups.entry(values.clone()).or_insert_with(|| Group { keys: values, ..Group::default() });
+
+        group.count += 1;
+        match tx.kind {
+            TransactionType::Credit => {
+                group.earned += tx.amount;
+                group.net += tx.amount;
+            }
+            TransactionType::Debit => {
+                group.spent += tx.amount;
+                group.net -= tx.amount;
+            }
+        }
+    }
+
+    if groups.is_empty() && keys.is_empty() {
+        return vec![Group::default()];
+    }
+
+    groups.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(source: &str, amount: f64, tag: &str, date: &str) -> Transaction {
+        Transaction {
+            id: 0,
+            source: source.into(),
+            amount,
+            kind: TransactionType::Debit,
+            tag: Tag::new(tag),
+            date: date.into(),
+            notes: String::new(),
+        }
+    }
+
+    fn matches(query: &str, tx: &Transaction) -> bool {
+        Query::parse(query).unwrap().matches(tx)
+    }
+
+    #[test]
+    fn ranges_expand_to_one_negated_token() {
+        let query = Query::parse("!date:2026-02..2026-06 amount:20..100").unwrap();
+
+        assert_eq!(query.terms.len(), 2);
+        assert!(
+            matches!(&query.terms[0], (true, terms) if matches!(terms.as_slice(), [Term::DateFrom(_), Term::DateTo(_)]))
+        );
+        assert!(
+            matches!(&query.terms[1], (false, terms) if matches!(terms.as_slice(), [Term::Amount(Comparison::GreaterEqual, _), Term::Amount(Comparison::LessEqual, _)]))
+        );
+    }
+
+    #[test]
+    fn open_ranges_keep_one_bound() {
+        let query = Query::parse("amount:..50 date:2026-03..").unwrap();
+
+        assert!(matches!(query.terms[0].1.as_slice(), [Term::Amount(Comparison::LessEqual, _)]));
+        assert!(matches!(query.terms[1].1.as_slice(), [Term::DateFrom(_)]));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(Query::parse("!").is_err());
+        assert!(Query::parse("amount:abc").is_err());
+        assert!(Query::parse("type:transfer").is_err());
+        assert!(Query::parse("colour:red").is_err());
+        assert!(Query::parse("date:2026-13").is_err());
+        assert!(Query::parse("source:/(unclosed/").is_err());
+        assert!(Query::parse("\"open quote").is_err());
+    }
+
+    #[test]
+    fn ranges_include_both_ends() {
+        assert!(matches("date:2026-02..2026-06", &tx("a", 1.0, "food", "2026-02-01")));
+        assert!(matches("date:2026-02..2026-06", &tx("a", 1.0, "food", "2026-06-30")));
+        assert!(!matches("date:2026-02..2026-06", &tx("a", 1.0, "food", "2026-07-01")));
+        assert!(matches("amount:20..100", &tx("a", 20.0, "food", "2026-02-01")));
+        assert!(matches("amount:20..100", &tx("a", 100.0, "food", "2026-02-01")));
+        assert!(!matches("amount:20..100", &tx("a", 100.5, "food", "2026-02-01")));
+    }
+
+    #[test]
+    fn negated_date_range_matches_outside_it() {
+        let query = "!date:2026-02..2026-06";
+
+        assert!(matches(query, &tx("a", 1.0, "food", "2026-01-31")));
+        assert!(matches(query, &tx("a", 1.0, "food", "2026-07-01")));
+        assert!(!matches(query, &tx("a", 1.0, "food", "2026-02-01")));
+        assert!(!matches(query, &tx("a", 1.0, "food", "2026-04-15")));
+        assert!(!matches(query, &tx("a", 1.0, "food", "2026-06-30")));
+    }
+
+    #[test]
+    fn negated_amount_range_matches_outside_it() {
+        let query = "!amount:20..100";
+
+        assert!(matches(query, &tx("a", 19.99, "food", "2026-05-01")));
+        assert!(matches(query, &tx("a", 150.0, "food", "2026-05-01")));
+        assert!(!matches(query, &tx("a", 20.0, "food", "2026-05-01")));
+        assert!(!matches(query, &tx("a", 60.0, "food", "2026-05-01")));
+    }
+
+    #[test]
+    fn negated_month_excludes_the_whole_month() {
+        let query = "!date:2026-05";
+
+        assert!(matches(query, &tx("a", 1.0, "food", "2026-04-30")));
+        assert!(matches(query, &tx("a", 1.0, "food", "2026-06-01")));
+        assert!(!matches(query, &tx("a", 1.0, "food", "2026-05-01")));
+        assert!(!matches(query, &tx("a", 1.0, "food", "2026-05-31")));
+    }
+
+    #[test]
+    fn negation_combines_with_other_terms() {
+        let query = "tag:food,travel !#travel amount>10";
+
+        assert!(matches(query, &tx("a", 11.0, "food", "2026-05-01")));
+        assert!(!matches(query, &tx("a", 11.0, "travel", "2026-05-01")));
+        assert!(!matches(query, &tx("a", 5.0, "food", "2026-05-01")));
+        assert!(!matches(query, &tx("a", 11.0, "bills", "2026-05-01")));
+    }
+
+    #[test]
+    fn glob_patterns_match_the_whole_source() {
+        assert!(matches("source:amazon*", &tx("Amazon Prime", 1.0, "food", "2026-05-01")));
+        assert!(!matches("source:amazon*", &tx("Pay Amazon", 1.0, "food", "2026-05-01")));
+        assert!(matches("source:uber?eats", &tx("Uber-Eats", 1.0, "food", "2026-05-01")));
+        // Regex characters in a glob are literal
+        assert!(matches("source:a.b*", &tx("a.b shop", 1.0, "food", "2026-05-01")));
+        assert!(!matches("source:a.b*", &tx("axb shop", 1.0, "food", "2026-05-01")));
+    }
+
+    #[test]
+    fn regex_and_substring_patterns() {
+        assert!(matches("\"source:/^uber (eats)?$/\"", &tx("Uber Eats", 1.0, "food", "2026-05-01")));
+        assert!(matches("\"source:/^uber (eats)?$/\"", &tx("uber ", 1.0, "food", "2026-05-01")));
+        assert!(!matches("\"source:/^uber (eats)?$/\"", &tx("Uber Eats NL", 1.0, "food", "2026-05-01")));
+        assert!(matches("source:mart", &tx("DMart", 1.0, "food", "2026-05-01")));
+        assert!(matches("\"d mart\"", &tx("Big D Mart", 1.0, "food", "2026-05-01")));
+    }
+}
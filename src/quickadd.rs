@@ -0,0 +1,501 @@
+use chrono::{ Datelike, Duration, NaiveDate, Weekday };
+use crossterm::event::KeyCode;
+use ratatui::{ prelude::*, widgets::{ Clear, Paragraph } };
+use rusqlite::Connection;
+
+use crate::{
+    app::{ App, Mode, NotificationLevel },
+    budget,
+    form::TransactionForm,
+    models::{ Tag, TransactionType },
+    theme::Theme,
+};
+
+/// Tag used when the line names none, if it is configured
+const FALLBACK_TAG: &str = "other";
+
+/// A one-line entry such as `120.5 groceries at DMart #food yesterday`, split
+/// into the fields of the transaction form
+#[derive(Debug, Clone)]
+pub struct QuickEntry {
+    pub source: String,
+    pub amount: Option<f64>,
+    pub kind: TransactionType,
+    pub tag: Option<Tag>,
+    // False when the tag is the fallback rather than typed or matched from a word
+    pub tag_given: bool,
+    pub date: NaiveDate,
+    // Reasons the entry can't be saved yet, in the order they should be fixed
+    pub problems: Vec<String>,
+}
+
+impl QuickEntry {
+    /// Copy the parsed fields into a fresh form, ready to submit or to finish editing
+    pub fn fill_form(&self, form: &mut TransactionForm, tags: &[Tag]) {
+        form.reset();
+        form.source = self.source.clone();
+        form.amount = self.amount.map(|a| a.to_string()).unwrap_or_default();
+        form.kind = self.kind;
+        form.tag_index = self.tag
+            .as_ref()
+            .and_then(|tag| tags.iter().position(|t| t == tag))
+            .unwrap_or(0);
+        form.date = self.date.format("%Y-%m-%d").to_string();
+    }
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+/// Read the amount (a leading `+` makes it income), `#tag`, date words and the
+/// remaining text as the source. Words may come in any order.
+pub fn parse(input: &str, tags: &[Tag], currency: &str, today: NaiveDate) -> QuickEntry {
+    let words: Vec<&str> = input.split_whitespace().collect();
+
+    let mut source: Vec<&str> = Vec::new();
+    let mut amount = None;
+    let mut kind = TransactionType::Debit;
+    let mut tag = None;
+    let mut date = None;
+    let mut problems = Vec::new();
+
+    let mut i = 0;
+    while i < words.len() {
+        let word = words[i];
+        let lower = word.to_lowercase();
+
+        if let Some(name) = word.strip_prefix('#').filter(|n| !n.is_empty()) {
+            match find_tag(name, tags) {
+                Some(found) => {
+                    tag = Some(found);
+                }
+                None => problems.push(format!("Unknown tag #{}", name)),
+            }
+        } else if let (None, Some((value, credit))) = (amount, parse_amount(word, currency)) {
+            amount = Some(value);
+            if credit {
+                kind = TransactionType::Credit;
+            }
+        } else if let Some(parsed) = parse_date_word(&lower, today) {
+            date = Some(parsed);
+        } else if let Some(parsed) = words.get(i + 1).and_then(|next| parse_prefixed_date(&lower, next, today)) {
+            // "on 15th", "last friday"
+            date = Some(parsed);
+            i += 1;
+        } else {
+            source.push(word);
+        }
+
+        i += 1;
+    }
+
+    // "+50000 salary" needs no #salary when a word already names a tag
+    let tag_given = tag.is_some() || source.iter().any(|w| find_tag(w, tags).is_some());
+    let tag = tag
+        .or_else(|| source.iter().find_map(|w| find_tag(w, tags)))
+        .or_else(|| find_tag(FALLBACK_TAG, tags))
+        .or_else(|| tags.first().cloned());
+
+    let source = source.join(" ");
+    if amount.is_none() {
+        problems.insert(0, "Add an amount, e.g. 120.5 or +500 for income".into());
+    }
+    if source.is_empty() {
+        problems.push("Add a description".into());
+    }
+
+    QuickEntry {
+        source,
+        amount,
+        kind,
+        tag,
+        tag_given,
+        date: date.unwrap_or(today),
+        problems,
+    }
+}
+
+fn find_tag(name: &str, tags: &[Tag]) -> Option<Tag> {
+    tags.iter()
+        .find(|t| t.as_str().eq_ignore_ascii_case(name))
+        .cloned()
+}
+
+/// `120.5`, `+50000`, `-12`, `$4.50`, `1,299`; the bool is true for income
+fn parse_amount(word: &str, currency: &str) -> Option<(f64, bool)> {
+    let (credit, rest) = match word.chars().next()? {
+        '+' => (true, &word[1..]),
+        '-' => (false, &word[1..]),
+        _ => (false, word),
+    };
+    let rest = rest.strip_prefix(currency).unwrap_or(rest);
+
+    // Must start with a digit so words like "inf" or "nan" stay in the source
+    if !rest.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+        return None;
+    }
+
+    let value: f64 = rest.replace([',', '_'], "").parse().ok()?;
+    (value.is_finite() && value > 0.0).then_some((value, credit))
+}
+
+/// Dates written as a single word: today, yesterday, a weekday (the latest one,
+/// today included), a day of the month like 1st or 15th (this month, or last
+/// month if that day hasn't come yet) or YYYY-MM-DD
+fn parse_date_word(word: &str, today: NaiveDate) -> Option<NaiveDate> {
+    match word {
+        "today" => {
+            return Some(today);
+        }
+        "yesterday" => {
+            return Some(today - Duration::days(1));
+        }
+        _ => {}
+    }
+
+    if let Some(weekday) = parse_weekday(word) {
+        let back = (7 + today.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+        return Some(today - Duration::days(back as i64));
+    }
+
+    if let Some(day) = parse_ordinal(word) {
+        let this_month = day_of_month(today.year(), today.month(), day);
+        if this_month <= today {
+            return Some(this_month);
+        }
+        let (year, month) = if today.month() == 1 { (today.year() - 1, 12) } else { (today.year(), today.month() - 1) };
+        return Some(day_of_month(year, month, day));
+    }
+
+    NaiveDate::parse_from_str(word, "%Y-%m-%d").ok()
+}
+
+/// Two-word dates: `on <date>` and `last <weekday>` (strictly before today)
+fn parse_prefixed_date(word: &str, next: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let next = next.to_lowercase();
+
+    match word {
+        "on" => parse_date_word(&next, today),
+        "last" => {
+            let weekday = parse_weekday(&next)?;
+            let back = (7 + today.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+            Some(today - Duration::days(if back == 0 { 7 } else { back as i64 }))
+        }
+        _ => None,
+    }
+}
+
+fn parse_weekday(word: &str) -> Option<Weekday> {
+    let weekday = match word {
+        "mon" | "monday" => Weekday::Mon,
+        "tue" | "tues" | "tuesday" => Weekday::Tue,
+        "wed" | "wednesday" => Weekday::Wed,
+        "thu" | "thur" | "thurs" | "thursday" => Weekday::Thu,
+        "fri" | "friday" => Weekday::Fri,
+        "sat" | "saturday" => Weekday::Sat,
+        "sun" | "sunday" => Weekday::Sun,
+        _ => {
+            return None;
+        }
+    };
+    Some(weekday)
+}
+
+fn parse_ordinal(word: &str) -> Option<u32> {
+    let digits = ["st", "nd", "rd", "th"].iter().find_map(|suffix| word.strip_suffix(suffix))?;
+    let day: u32 = digits.parse().ok()?;
+    (1..=31).contains(&day).then_some(day)
+}
+
+/// `day` in the given month, clamped for short months (31st in April is the 30th)
+fn day_of_month(year: i32, month: u32, day: u32) -> NaiveDate {
+    (1..=day)
+        .rev()
+        .find_map(|d| NaiveDate::from_ymd_opt(year, month, d))
+        .expect("every month has a first day")
+}
+
+// ============================================================================
+// Quick add UI rendering functions
+// ============================================================================
+
+pub fn draw_quick_add_view(f: &mut Frame, app: &App, theme: &Theme) {
+    let entry = parse(&app.quick_add, &app.tags, &app.currency, app.today);
+    let label = |text: &str| Span::styled(format!("  {:<8}", text), theme.muted_text());
+
+    let mut lines = vec![
+        Line::raw(""),
+        Line::from(
+            vec![
+                Span::styled("  › ", Style::default().fg(theme.accent)),
+                Span::styled(format!("{}│", app.quick_add), Style::default().fg(theme.foreground))
+            ]
+        ),
+        Line::styled(
+            "    e.g. 120.5 groceries at DMart #food yesterday   +50000 salary 1st",
+            Style::default().fg(theme.subtle).add_modifier(Modifier::ITALIC)
+        ),
+        Line::raw("")
+    ];
+
+    let missing = Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC);
+
+    lines.push(
+        Line::from(
+            vec![
+                label("Source"),
+                if entry.source.is_empty() {
+                    Span::styled("—", missing)
+                } else {
+                    Span::styled(entry.source.clone(), Style::default().fg(theme.foreground))
+                }
+            ]
+        )
+    );
+
+    let color = theme.transaction_color(entry.kind);
+    lines.push(
+        Line::from(match entry.amount {
+            Some(amount) => {
+                let sign = if entry.kind == TransactionType::Credit { "+" } else { "-" };
+                vec![
+                    label("Amount"),
+                    Span::styled(
+                        format!("{}{}{:.2}", sign, app.currency, amount),
+                        Style::default().fg(color).add_modifier(Modifier::BOLD)
+                    ),
+                    Span::styled(format!("  {}", entry.kind.as_str()), theme.muted_text())
+                ]
+            }
+            None => vec![label("Amount"), Span::styled("—", missing)],
+        })
+    );
+
+    lines.push(
+        Line::from(match &entry.tag {
+            Some(tag) =>
+                vec![
+                    label("Tag"),
+                    Span::styled(format!("#{}", tag.as_str()), Style::default().fg(theme.accent_soft)),
+                    Span::styled(if entry.tag_given { "" } else { "  (default)" }, theme.muted_text())
+                ],
+            None => vec![label("Tag"), Span::styled("no tags configured", missing)],
+        })
+    );
+
+    let relative = match (app.today - entry.date).num_days() {
+        0 => "  today".to_string(),
+        1 => "  yesterday".to_string(),
+        _ => format!("  {}", entry.date.format("%A")),
+    };
+    lines.push(
+        Line::from(
+            vec![
+                label("Date"),
+                Span::styled(entry.date.format("%Y-%m-%d").to_string(), Style::default().fg(theme.foreground)),
+                Span::styled(relative, theme.muted_text())
+            ]
+        )
+    );
+
+    lines.push(Line::raw(""));
+
+    // Warn before saving rather than after, like the over-budget confirmation does
+    let breaches = match (&entry.tag, entry.amount, entry.kind) {
+        (Some(tag), Some(amount), TransactionType::Debit) =>
+            budget::check_entry(&app.transactions, &app.budgets, tag, amount, entry.date, None),
+        _ => Vec::new(),
+    };
+
+    if let Some(problem) = entry.problems.first() {
+        lines.push(Line::styled(format!("  {}", problem), Style::default().fg(theme.warning)));
+    } else if !breaches.is_empty() {
+        lines.push(
+            Line::styled(
+                format!("  {}", budget::describe_breaches(&breaches, &app.currency)),
+                theme.danger()
+            )
+        );
+    } else {
+        lines.push(Line::styled("  ✓ Ready to save", theme.success()));
+    }
+
+    lines.push(Line::raw(""));
+    lines.push(
+        Line::from(
+            vec![
+                Span::styled("  [", theme.muted_text()),
+                Span::styled("Enter", Style::default().fg(theme.credit)),
+                Span::styled("] Save  [", theme.muted_text()),
+                Span::styled("Tab", Style::default().fg(theme.accent)),
+                Span::styled("] Full Form  [", theme.muted_text()),
+                Span::styled("Esc", Style::default().fg(theme.debit)),
+                Span::styled("] Cancel", theme.muted_text())
+            ]
+        )
+    );
+
+    let screen = f.size();
+    let width = 76.min(screen.width);
+    let height = ((lines.len() as u16) + 2).min(screen.height);
+    let area = Rect {
+        x: (screen.width - width) / 2,
+        y: (screen.height - height) / 2,
+        width,
+        height,
+    };
+
+    let popup = Paragraph::new(lines).block(theme.popup("⚡ Quick Add"));
+
+    f.render_widget(Clear, area);
+    f.render_widget(popup, area);
+}
+
+// ============================================================================
+// Quick add input handler
+// ============================================================================
+
+/// Enter saves through the same path as the form (rules, budget confirmation,
+/// duplicate warning, hooks); Tab moves the parsed fields into the full form
+pub fn handle_quick_add(app: &mut App, key: KeyCode, conn: &Connection) -> bool {
+    match key {
+        KeyCode::Esc => {
+            app.quick_add.clear();
+            app.mode = Mode::Normal;
+        }
+        KeyCode::Backspace => {
+            app.quick_add.pop();
+        }
+        KeyCode::Char(c) => {
+            app.quick_add.push(c);
+        }
+        KeyCode::Tab => {
+            let entry = parse(&app.quick_add, &app.tags, &app.currency, app.today);
+            entry.fill_form(&mut app.form, &app.tags);
+            app.editing = None;
+            app.quick_add.clear();
+            app.mode = Mode::Adding;
+        }
+        KeyCode::Enter => {
+            let entry = parse(&app.quick_add, &app.tags, &app.currency, app.today);
+            if let Some(problem) = entry.problems.first() {
+                app.notify(problem.clone(), NotificationLevel::Warning);
+                return false;
+            }
+
+            entry.fill_form(&mut app.form, &app.tags);
+            app.editing = None;
+            app.quick_add.clear();
+            app.submit_form(conn);
+        }
+        _ => {}
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(text: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap()
+    }
+
+    // A Sunday
+    fn today() -> NaiveDate {
+        date("2026-10-18")
+    }
+
+    fn quick(input: &str) -> QuickEntry {
+        let tags: Vec<Tag> = ["food", "travel", "bills", "salary", "other"]
+            .iter()
+            .map(|t| Tag::new(t))
+            .collect();
+        parse(input, &tags, "$", today())
+    }
+
+    #[test]
+    fn splits_amount_tag_date_and_source() {
+        let entry = quick("120.5 groceries at DMart #food yesterday");
+        assert_eq!(entry.amount, Some(120.5));
+        assert_eq!(entry.kind, TransactionType::Debit);
+        assert_eq!(entry.tag, Some(Tag::new("food")));
+        assert!(entry.tag_given);
+        assert_eq!(entry.date, date("2026-10-17"));
+        assert_eq!(entry.source, "groceries at DMart");
+        assert!(entry.problems.is_empty());
+    }
+
+    #[test]
+    fn amounts() {
+        let income = quick("+50000 salary");
+        assert_eq!(income.amount, Some(50000.0));
+        assert_eq!(income.kind, TransactionType::Credit);
+
+        let expense = quick("-12 lunch");
+        assert_eq!(expense.amount, Some(12.0));
+        assert_eq!(expense.kind, TransactionType::Debit);
+
+        assert_eq!(quick("$12 lunch").amount, Some(12.0));
+        assert_eq!(quick("rent 1,200 2026-10-01 #bills").amount, Some(1200.0));
+        assert_eq!(quick("rent 1,200 2026-10-01 #bills").date, date("2026-10-01"));
+    }
+
+    #[test]
+    fn words_that_parse_as_floats_stay_in_the_source() {
+        let entry = quick("inf nan");
+        assert_eq!(entry.amount, None);
+        assert_eq!(entry.source, "inf nan");
+        assert_eq!(entry.problems[0], "Add an amount, e.g. 120.5 or +500 for income");
+    }
+
+    #[test]
+    fn tags() {
+        // Typed tags match case-insensitively
+        assert_eq!(quick("$12 lunch #Food").tag, Some(Tag::new("food")));
+
+        // A word naming a tag counts as giving it
+        let named = quick("+50000 salary 25th");
+        assert_eq!(named.tag, Some(Tag::new("salary")));
+        assert!(named.tag_given);
+
+        let unknown = quick("spent on stuff 5 #nope");
+        assert_eq!(unknown.problems, vec!["Unknown tag #nope".to_string()]);
+        assert_eq!(unknown.tag, Some(Tag::new("other")));
+        assert!(!unknown.tag_given);
+        assert_eq!(unknown.source, "spent on stuff");
+    }
+
+    #[test]
+    fn ordinal_days() {
+        // Already passed this month
+        assert_eq!(quick("+50000 salary #salary 1st").date, date("2026-10-01"));
+        assert_eq!(quick("lunch 12 18th").date, today());
+
+        // Not yet come, so last month
+        assert_eq!(quick("+50000 salary 25th").date, date("2026-09-25"));
+
+        // Clamped to the end of a short month
+        assert_eq!(quick("31st phone 20").date, date("2026-09-30"));
+    }
+
+    #[test]
+    fn weekdays() {
+        // The latest one, today included
+        assert_eq!(quick("sunday brunch 40").date, today());
+        assert_eq!(quick("$12 lunch on friday #Food").date, date("2026-10-16"));
+        assert_eq!(quick("lunch 12 mon").date, date("2026-10-12"));
+    }
+
+    #[test]
+    fn last_weekday_is_strictly_before_today() {
+        assert_eq!(quick("taxi 300 last sunday #travel").date, date("2026-10-11"));
+        assert_eq!(quick("taxi 300 last saturday").date, date("2026-10-17"));
+
+        let entry = quick("taxi 300 last sunday #travel");
+        assert_eq!(entry.source, "taxi");
+        assert_eq!(entry.tag, Some(Tag::new("travel")));
+    }
+}
@@ -0,0 +1,269 @@
+use std::{ cell::RefCell, fs, path::{ Path, PathBuf }, rc::Rc };
+
+use chrono::Local;
+use rhai::{ Array, Dynamic, Engine, EvalAltResult, Map, Scope };
+
+use crate::{
+    models::{ Transaction, TransactionType },
+    profile,
+    query::Query,
+    stats::StatsSnapshot,
+};
+
+/// Stops runaway scripts (e.g. an endless loop) from freezing the TUI
+const MAX_OPERATIONS: u64 = 10_000_000;
+
+/// Stop scripts from exhausting memory (e.g. doubling a string in a loop),
+/// while leaving room for a copy of a large ledger
+const MAX_STRING_SIZE: usize = 1_000_000;
+const MAX_ARRAY_SIZE: usize = 1_000_000;
+const MAX_MAP_SIZE: usize = 1_000_000;
+
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub name: String,
+    pub output: ReportOutput,
+}
+
+#[derive(Debug, Clone)]
+pub enum ReportOutput {
+    Text(String),
+    Table {
+        // Lines printed by the script before returning the table
+        text: String,
+        headers: Vec<String>,
+        rows: Vec<Vec<String>>,
+    },
+    Error(String),
+}
+
+/// Report scripts live in a `reports` folder next to the active config.yaml
+pub fn reports_dir() -> PathBuf {
+    let config = profile::active().config;
+    config
+        .parent()
+        .map(|dir| dir.join("reports"))
+        .unwrap_or_else(|| PathBuf::from("reports"))
+}
+
+/// Every `*.rhai` file in the reports folder, sorted by name
+pub fn list_scripts() -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(reports_dir()) else {
+        return Vec::new();
+    };
+
+    let mut scripts: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "rhai"))
+        .collect();
+    scripts.sort();
+    scripts
+}
+
+pub fn script_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+pub fn run_all(transactions: &[Transaction], snapshot: &StatsSnapshot, currency: &str) -> Vec<Report> {
+    list_scripts()
+        .iter()
+        .map(|path| run_report(path, transactions, snapshot, currency))
+        .collect()
+}
+
+// ============================================================================
+// Script execution
+// ============================================================================
+
+/// Run one script. Scripts see `transactions`, `stats`, `currency` and `today`,
+/// plus `query(expr)`, `sum(list)` and `money(amount)`. Whatever they `print`
+/// and return becomes the report.
+pub fn run_report(path: &Path, transactions: &[Transaction], snapshot: &StatsSnapshot, currency: &str) -> Report {
+    let name = script_name(path);
+    let output = match fs::read_to_string(path) {
+        Ok(script) => evaluate(&script, transactions, snapshot, currency),
+        Err(e) => ReportOutput::Error(format!("Could not read {}: {}", path.display(), e)),
+    };
+
+    Report { name, output }
+}
+
+fn evaluate(script: &str, transactions: &[Transaction], snapshot: &StatsSnapshot, currency: &str) -> ReportOutput {
+    let printed = Rc::new(RefCell::new(Vec::<String>::new()));
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_string_size(MAX_STRING_SIZE);
+    engine.set_max_array_size(MAX_ARRAY_SIZE);
+    engine.set_max_map_size(MAX_MAP_SIZE);
+
+    let sink = printed.clone();
+    engine.on_print(move |line| sink.borrow_mut().push(line.to_string()));
+
+    let all = transactions.to_vec();
+    engine.register_fn("query", move |expr: &str| -> Result<Array, Box<EvalAltResult>> {
+        let query = Query::parse(expr)?;
+        Ok(
+            query
+                .apply(&all)
+                .iter()
+                .map(|tx| Dynamic::from_map(transaction_map(tx)))
+                .collect()
+        )
+    });
+    engine.register_fn("sum", |list: Array| -> f64 {
+        list.iter()
+            .filter_map(|item| {
+                item.read_lock::<Map>()
+                    .and_then(|m| m.get("amount").and_then(|a| a.as_float().ok()))
+                    .or_else(|| item.as_float().ok())
+                    .or_else(|| item.as_int().ok().map(|i| i as f64))
+            })
+            .sum()
+    });
+    let symbol = currency.to_string();
+    engine.register_fn("money", move |amount: f64| format!("{}{:.2}", symbol, amount));
+
+    let mut scope = Scope::new();
+    scope.push_constant(
+        "transactions",
+        transactions
+            .iter()
+            .map(|tx| Dynamic::from_map(transaction_map(tx)))
+            .collect::<Array>()
+    );
+    scope.push_constant("stats", stats_map(snapshot));
+    scope.push_constant("currency", currency.to_string());
+    scope.push_constant("today", Local::now().format("%Y-%m-%d").to_string());
+
+    let result = engine.eval_with_scope::<Dynamic>(&mut scope, script);
+    let text = printed.borrow().join("\n");
+
+    match result {
+        Ok(value) => to_output(text, value),
+        Err(e) => ReportOutput::Error(e.to_string()),
+    }
+}
+
+fn transaction_map(tx: &Transaction) -> Map {
+    let signed = match tx.kind {
+        TransactionType::Credit => tx.amount,
+        TransactionType::Debit => -tx.amount,
+    };
+
+    let mut map = Map::new();
+    map.insert("id".into(), (tx.id as i64).into());
+    map.insert("date".into(), tx.date.clone().into());
+    map.insert("month".into(), tx.date.get(..7).unwrap_or(&tx.date).to_string().into());
+    map.insert("source".into(), tx.source.clone().into());
+    map.insert("amount".into(), tx.amount.into());
+    map.insert("signed".into(), signed.into());
+    map.insert("type".into(), tx.kind.as_str().to_string().into());
+    map.insert("tag".into(), tx.tag.as_str().to_string().into());
+    map.insert("notes".into(), tx.notes.clone().into());
+    map
+}
+
+fn stats_map(snapshot: &StatsSnapshot) -> Map {
+    let per_tag: Map = snapshot.per_tag
+        .iter()
+        .map(|(tag, amount)| (tag.as_str().into(), Dynamic::from(*amount)))
+        .collect();
+
+    let monthly: Array = snapshot.monthly_history
+        .iter()
+        .map(|(month, earned, spent)| {
+            let mut row = Map::new();
+            row.insert("month".into(), month.clone().into());
+            row.insert("earned".into(), (*earned).into());
+            row.insert("spent".into(), (*spent).into());
+            Dynamic::from_map(row)
+        })
+        .collect();
+
+    let mut map = Map::new();
+    map.insert("earned".into(), snapshot.earned.into());
+    map.insert("spent".into(), snapshot.spent.into());
+    map.insert("balance".into(), snapshot.balance.into());
+    map.insert("count".into(), (snapshot.tx_count as i64).into());
+    map.insert("per_tag".into(), Dynamic::from_map(per_tag));
+    map.insert("monthly".into(), monthly.into());
+    map
+}
+
+/// Strings and other values become text; a list of lists (header row first),
+/// a list of maps, or `#{ columns: [...], rows: [...] }` becomes a table
+fn to_output(text: String, value: Dynamic) -> ReportOutput {
+    let join = |extra: String| {
+        match (text.is_empty(), extra.is_empty()) {
+            (_, true) => text.clone(),
+            (true, false) => extra,
+            (false, false) => format!("{}\n{}", text, extra),
+        }
+    };
+
+    if value.is_unit() {
+        return ReportOutput::Text(text);
+    }
+    if value.is_string() {
+        return ReportOutput::Text(join(value.into_string().unwrap_or_default()));
+    }
+
+    if let Some(map) = value.read_lock::<Map>().map(|m| m.clone())
+        && let Some(rows) = map.get("rows").and_then(|r| r.read_lock::<Array>().map(|a| a.clone()))
+    {
+        let headers = map
+            .get("columns")
+            .and_then(|c| c.read_lock::<Array>().map(|a| a.iter().map(cell).collect()))
+            .unwrap_or_default();
+        return table(text, headers, &rows);
+    }
+
+    if let Some(items) = value.read_lock::<Array>().map(|a| a.clone()) {
+        if let Some(first) = items.first().and_then(|f| f.read_lock::<Map>().map(|m| m.clone())) {
+            let keys: Vec<String> = first.keys().map(|k| k.to_string()).collect();
+            let rows: Vec<Vec<String>> = items
+                .iter()
+                .map(|item| {
+                    let map = item.read_lock::<Map>().map(|m| m.clone()).unwrap_or_default();
+                    keys.iter()
+                        .map(|k| map.get(k.as_str()).map(cell).unwrap_or_default())
+                        .collect()
+                })
+                .collect();
+            return ReportOutput::Table { text, headers: keys, rows };
+        }
+
+        if items.first().is_some_and(|f| f.is_array()) {
+            let headers = items[0]
+                .read_lock::<Array>()
+                .map(|row| row.iter().map(cell).collect())
+                .unwrap_or_default();
+            return table(text, headers, &items[1..]);
+        }
+    }
+
+    ReportOutput::Text(join(cell(&value)))
+}
+
+fn table(text: String, headers: Vec<String>, rows: &[Dynamic]) -> ReportOutput {
+    let rows = rows
+        .iter()
+        .map(|row| {
+            row.read_lock::<Array>()
+                .map(|cells| cells.iter().map(cell).collect())
+                .unwrap_or_else(|| vec![cell(row)])
+        })
+        .collect();
+
+    ReportOutput::Table { text, headers, rows }
+}
+
+fn cell(value: &Dynamic) -> String {
+    match value.as_float() {
+        Ok(number) => format!("{:.2}", number),
+        Err(_) => value.to_string(),
+    }
+}
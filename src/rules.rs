@@ -0,0 +1,230 @@
+use regex::Regex;
+use rusqlite::Connection;
+use serde::{ Deserialize, Serialize };
+
+use crate::{
+    db,
+    hooks::{ self, Event, Hooks, WithWarnings },
+    models::{ Tag, Transaction, TransactionType },
+};
+
+/// One categorisation rule from `rules:` in config.yaml. Rules are tried in
+/// order and the first one whose conditions all match is applied.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Rule {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default, rename = "match")]
+    pub conditions: RuleMatch,
+    #[serde(default)]
+    pub set: RuleActions,
+    // Leave matching rows out of imports
+    #[serde(default)]
+    pub skip: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct RuleMatch {
+    // Regular expression tested against the source/payee
+    #[serde(default)]
+    pub source: Option<String>,
+    #[serde(default)]
+    pub min_amount: Option<f64>,
+    #[serde(default)]
+    pub max_amount: Option<f64>,
+    // "debit" or "credit"
+    #[serde(default, rename = "type")]
+    pub kind: Option<String>,
+    // Statement account id (OFX imports only)
+    #[serde(default)]
+    pub account: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct RuleActions {
+    #[serde(default)]
+    pub tag: Option<String>,
+    #[serde(default)]
+    pub source: Option<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+/// What a rule looks at
+pub struct RuleInput<'a> {
+    pub source: &'a str,
+    pub amount: f64,
+    pub kind: TransactionType,
+    pub account: Option<&'a str>,
+}
+
+/// What a matching rule changes
+#[derive(Debug, Clone, Default)]
+pub struct RuleOutcome {
+    pub rule: String,
+    pub tag: Option<Tag>,
+    pub source: Option<String>,
+    pub notes: Option<String>,
+    pub skip: bool,
+}
+
+struct CompiledRule {
+    rule: Rule,
+    pattern: Option<Regex>,
+    label: String,
+}
+
+/// Rules with their source patterns compiled
+pub struct RuleSet {
+    rules: Vec<CompiledRule>,
+}
+
+impl RuleSet {
+    /// Compile rules, collecting a message for each invalid pattern; broken
+    /// rules are dropped rather than matching everything.
+    pub fn new(rules: &[Rule]) -> (Self, Vec<String>) {
+        let mut compiled = Vec::new();
+        let mut errors = Vec::new();
+
+        for (i, rule) in rules.iter().enumerate() {
+            let pattern = match rule.conditions.source.as_deref().map(Regex::new).transpose() {
+                Ok(p) => p,
+                Err(e) => {
+                    errors.push(format!("Rule {}: invalid source pattern: {}", rule_label(rule, i), e));
+                    continue;
+                }
+            };
+            compiled.push(CompiledRule {
+                rule: rule.clone(),
+                pattern,
+                label: rule_label(rule, i),
+            });
+        }
+
+        (Self { rules: compiled }, errors)
+    }
+
+    /// First rule matching `input`, with tags limited to the configured ones
+    pub fn apply(&self, input: &RuleInput, tags: &[Tag]) -> Option<RuleOutcome> {
+        let CompiledRule { rule, label, .. } = self.rules
+            .iter()
+            .find(|c| matches(&c.rule, c.pattern.as_ref(), input))?;
+
+        Some(RuleOutcome {
+            rule: label.clone(),
+            tag: rule.set.tag
+                .as_deref()
+                .map(Tag::new)
+                .filter(|t| tags.contains(t)),
+            source: rule.set.source.clone(),
+            notes: rule.set.notes.clone(),
+            skip: rule.skip,
+        })
+    }
+}
+
+fn rule_label(rule: &Rule, index: usize) -> String {
+    rule.name.clone().unwrap_or_else(|| format!("#{}", index + 1))
+}
+
+fn matches(rule: &Rule, pattern: Option<&Regex>, input: &RuleInput) -> bool {
+    let c = &rule.conditions;
+
+    if let Some(re) = pattern && !re.is_match(input.source) {
+        return false;
+    }
+    if c.min_amount.is_some_and(|min| input.amount < min) {
+        return false;
+    }
+    if c.max_amount.is_some_and(|max| input.amount > max) {
+        return false;
+    }
+    if let Some(kind) = &c.kind && !kind.eq_ignore_ascii_case(input.kind.as_str()) {
+        return false;
+    }
+    if let Some(account) = &c.account && input.account != Some(account.as_str()) {
+        return false;
+    }
+
+    true
+}
+
+// ============================================================================
+// Re-running rules over existing transactions
+// ============================================================================
+
+/// A change rules would make to a stored transaction
+#[derive(Debug, Clone)]
+pub struct RuleChange {
+    pub rule: String,
+    pub before: Transaction,
+    pub source: String,
+    pub tag: Tag,
+    pub notes: String,
+}
+
+impl RuleChange {
+    /// The transaction as it reads once the change is written
+    pub fn after(&self) -> Transaction {
+        Transaction {
+            source: self.source.clone(),
+            tag: self.tag.clone(),
+            notes: self.notes.clone(),
+            ..self.before.clone()
+        }
+    }
+}
+
+/// Dry run: what applying the rules to `transactions` would change
+pub fn plan_changes(rules: &RuleSet, transactions: &[Transaction], tags: &[Tag]) -> Vec<RuleChange> {
+    transactions
+        .iter()
+        .filter_map(|tx| {
+            let input = RuleInput {
+                source: &tx.source,
+                amount: tx.amount,
+                kind: tx.kind,
+                account: None,
+            };
+            let outcome = rules.apply(&input, tags)?;
+
+            let change = RuleChange {
+                rule: outcome.rule,
+                before: tx.clone(),
+                source: outcome.source.unwrap_or_else(|| tx.source.clone()),
+                tag: outcome.tag.unwrap_or_else(|| tx.tag.clone()),
+                notes: outcome.notes.unwrap_or_else(|| tx.notes.clone()),
+            };
+
+            let unchanged =
+                change.source == tx.source && change.tag == tx.tag && change.notes == tx.notes;
+            if unchanged { None } else { Some(change) }
+        })
+        .collect()
+}
+
+/// Write planned changes in a single SQLite transaction, then fire
+/// `transaction_updated` for each
+pub fn apply_changes(conn: &Connection, changes: &[RuleChange], hooks: &Hooks) -> Result<WithWarnings<usize>, String> {
+    write_changes(conn, changes).map_err(|e| e.to_string())?;
+
+    let after: Vec<Transaction> = changes.iter().map(RuleChange::after).collect();
+    let events: Vec<Event> = changes
+        .iter()
+        .zip(&after)
+        .map(|(change, after)| Event::TransactionUpdated { before: &change.before, after })
+        .collect();
+    let warnings = hooks::fire_all(hooks, &events);
+
+    Ok(WithWarnings { value: changes.len(), warnings })
+}
+
+fn write_changes(conn: &Connection, changes: &[RuleChange]) -> rusqlite::Result<()> {
+    let tx = conn.unchecked_transaction()?;
+
+    for change in changes {
+        db::recategorize_transaction(&tx, change.before.id, &change.source, &change.tag, &change.notes)?;
+    }
+
+    tx.commit()
+}
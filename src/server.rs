@@ -0,0 +1,425 @@
+use std::{ collections::HashMap, io::Read };
+
+use chrono::Local;
+use rusqlite::Connection;
+use serde::Deserialize;
+use serde_json::{ json, Value };
+use tiny_http::{ Header, Method, Request, Response, Server };
+
+use fitui::{
+    budget::{ BudgetStatus, Budgets },
+    config::{ load_config, Config },
+    db,
+    entry::{ add_entry, add_recurring, update_entry, NewEntry },
+    filter::parse_date_bound,
+    hooks::{ self, Event },
+    models::{ Tag, Transaction, TransactionType },
+    query::Query,
+    stats::StatsSnapshot,
+};
+
+/// Larger request bodies are refused with 413
+const MAX_BODY_BYTES: u64 = 1 << 20;
+
+/// Error response: HTTP status and a message sent as `{"error": "..."}`
+struct ApiError(u16, String);
+
+impl ApiError {
+    fn bad_request(message: impl Into<String>) -> Self {
+        ApiError(400, message.into())
+    }
+
+    fn not_found(message: impl Into<String>) -> Self {
+        ApiError(404, message.into())
+    }
+}
+
+impl From<rusqlite::Error> for ApiError {
+    fn from(e: rusqlite::Error) -> Self {
+        ApiError(500, e.to_string())
+    }
+}
+
+type ApiResult = Result<(u16, Value), ApiError>;
+
+/// Fields accepted when creating or updating a transaction. Omitted fields keep
+/// their current value on update.
+#[derive(Deserialize)]
+struct TransactionBody {
+    source: Option<String>,
+    amount: Option<f64>,
+    #[serde(rename = "type", alias = "kind")]
+    kind: Option<TransactionType>,
+    tag: Option<String>,
+    date: Option<String>,
+    notes: Option<String>,
+    // Confirms an expense over budget when budget_hard_limit is on
+    #[serde(default)]
+    force: bool,
+}
+
+#[derive(Deserialize)]
+struct RecurringBody {
+    source: String,
+    amount: f64,
+    #[serde(rename = "type", alias = "kind", default = "default_kind")]
+    kind: TransactionType,
+    #[serde(default = "default_tag")]
+    tag: String,
+    day: u32,
+}
+
+fn default_kind() -> TransactionType {
+    TransactionType::Debit
+}
+
+fn default_tag() -> String {
+    "other".into()
+}
+
+// ============================================================================
+// Server loop
+// ============================================================================
+
+/// Serve the JSON API until the process is stopped. Requests are handled one
+/// at a time on the given connection; config is re-read for every request.
+pub fn serve(conn: &Connection, listen: &str, token: Option<&str>) -> Result<(), String> {
+    let server = Server::http(listen).map_err(|e| format!("Could not listen on {}: {}", listen, e))?;
+
+    let loopback = listen.starts_with("127.") || listen.starts_with("localhost") || listen.starts_with("[::1]");
+    if token.is_none() && !loopback {
+        eprintln!("warning: serving on {} without --token; anyone on the network can change your data", listen);
+    }
+    eprintln!("Listening on http://{}", listen);
+
+    for mut request in server.incoming_requests() {
+        let (status, body) = match authorize(&request, token) {
+            Ok(()) => handle(conn, &mut request),
+            Err(e) => Err(e),
+        }.unwrap_or_else(|ApiError(status, message)| (status, json!({ "error": message })));
+
+        eprintln!("{} {} {}", request.method(), request.url(), status);
+
+        let content_type = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).expect("static header is valid");
+        let response = Response::from_string(body.to_string()).with_status_code(status).with_header(content_type);
+        if let Err(e) = request.respond(response) {
+            eprintln!("warning: could not send response: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+fn authorize(request: &Request, token: Option<&str>) -> Result<(), ApiError> {
+    let Some(token) = token else {
+        return Ok(());
+    };
+
+    // The health check stays open so monitors don't need the token
+    if request.url() == "/api/health" {
+        return Ok(());
+    }
+
+    let expected = format!("Bearer {}", token);
+    let authorized = request
+        .headers()
+        .iter()
+        .any(|h| h.field.equiv("Authorization") && h.value.as_str() == expected);
+
+    if authorized { Ok(()) } else { Err(ApiError(401, "missing or invalid bearer token".into())) }
+}
+
+fn handle(conn: &Connection, request: &mut Request) -> ApiResult {
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+    let params = parse_query_string(query);
+    let segments: Vec<&str> = path
+        .trim_matches('/')
+        .split('/')
+        .collect();
+
+    // One byte over the limit is enough to tell the body is too large
+    let mut body = String::new();
+    request
+        .as_reader()
+        .take(MAX_BODY_BYTES + 1)
+        .read_to_string(&mut body)
+        .map_err(|e| ApiError::bad_request(format!("could not read body: {}", e)))?;
+    if body.len() as u64 > MAX_BODY_BYTES {
+        return Err(ApiError(413, format!("request body is larger than {} bytes", MAX_BODY_BYTES)));
+    }
+
+    let config = load_config();
+    let method = request.method().clone();
+
+    match (&method, segments.as_slice()) {
+        (Method::Get, ["api", "health"]) => Ok((200, json!({ "status": "ok" }))),
+        (Method::Get, ["api", "transactions"]) => list_transactions(conn, &params),
+        (Method::Post, ["api", "transactions"]) => create_transaction(conn, &config, &body),
+        (Method::Get, ["api", "transactions", id]) => Ok((200, json!(find_transaction(conn, parse_id(id)?)?))),
+        (Method::Put, ["api", "transactions", id]) => update_transaction(conn, &config, parse_id(id)?, &body),
+        (Method::Delete, ["api", "transactions", id]) => {
+            let tx = find_transaction(conn, parse_id(id)?)?;
+            db::delete_transaction(conn, tx.id)?;
+            log_hook_errors(hooks::fire(&config.hooks, &Event::TransactionDeleted(&tx)));
+            Ok((200, json!(tx)))
+        }
+        (Method::Get, ["api", "recurring"]) => Ok((200, json!(db::get_recurring_entries(conn)?))),
+        (Method::Post, ["api", "recurring"]) => create_recurring(conn, &config, &body),
+        (Method::Post, ["api", "recurring", "run"]) => run_recurring(conn, &config, &params),
+        (Method::Delete, ["api", "recurring", id]) => {
+            let id = parse_id(id)?;
+            let entry = db
+                ::get_recurring_entries(conn)?
+                .into_iter()
+                .find(|e| e.id == id)
+                .ok_or_else(|| ApiError::not_found(format!("no recurring entry with id {}", id)))?;
+            db::delete_recurring_entry(conn, id)?;
+            Ok((200, json!(entry)))
+        }
+        (Method::Get, ["api", "tags"]) => Ok((200, tags_json(&config))),
+        (Method::Get, ["api", "stats"]) => stats(conn, &config, &params),
+        // Known routes called with another method
+        (_, ["api", "health" | "transactions" | "recurring" | "tags" | "stats"] | ["api", "transactions" | "recurring", _]) =>
+            Err(ApiError(405, format!("{} {} is not supported", method, path))),
+        _ => Err(ApiError::not_found(format!("no route for {}", path))),
+    }
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+fn list_transactions(conn: &Connection, params: &HashMap<String, String>) -> ApiResult {
+    let query = Query::parse(params.get("q").map(String::as_str).unwrap_or("")).map_err(ApiError::bad_request)?;
+
+    let mut transactions = query.apply(&db::get_transactions(conn)?);
+    transactions.sort_by(|a, b| b.date.cmp(&a.date).then(b.id.cmp(&a.id)));
+
+    if let Some(limit) = params.get("limit") {
+        let limit: usize = limit.parse().map_err(|_| ApiError::bad_request(format!("invalid limit '{}'", limit)))?;
+        transactions.truncate(limit);
+    }
+
+    Ok((200, json!(transactions)))
+}
+
+fn create_transaction(conn: &Connection, config: &Config, body: &str) -> ApiResult {
+    let body: TransactionBody = parse_body(body)?;
+
+    let entry = NewEntry {
+        source: body.source.ok_or_else(|| ApiError::bad_request("source is required"))?,
+        amount: body.amount.ok_or_else(|| ApiError::bad_request("amount is required"))?,
+        kind: body.kind.unwrap_or(TransactionType::Debit),
+        tag: body.tag.unwrap_or_else(default_tag),
+        date: body.date,
+        notes: body.notes.unwrap_or_default(),
+        force: body.force,
+    };
+    let added = add_entry(conn, config, entry).map_err(ApiError::bad_request)?;
+
+    let breaches: Vec<Value> = added.breaches.iter().map(budget_json).collect();
+    Ok((
+        201,
+        json!({
+            "transaction": added.transaction,
+            "budget_exceeded": breaches,
+            "possible_duplicate": added.duplicate,
+            "warnings": added.warnings,
+        }),
+    ))
+}
+
+fn update_transaction(conn: &Connection, config: &Config, id: i32, body: &str) -> ApiResult {
+    let body: TransactionBody = parse_body(body)?;
+    let before = find_transaction(conn, id)?;
+
+    // Fields left out keep their stored value
+    let entry = NewEntry {
+        source: body.source.unwrap_or(before.source),
+        amount: body.amount.unwrap_or(before.amount),
+        kind: body.kind.unwrap_or(before.kind),
+        tag: body.tag.unwrap_or(before.tag.0),
+        date: Some(body.date.unwrap_or(before.date)),
+        notes: body.notes.unwrap_or(before.notes),
+        force: body.force,
+    };
+    let updated = update_entry(conn, config, id, entry).map_err(ApiError::bad_request)?;
+
+    let breaches: Vec<Value> = updated.breaches.iter().map(budget_json).collect();
+    Ok((
+        200,
+        json!({
+            "transaction": updated.transaction,
+            "budget_exceeded": breaches,
+            "warnings": updated.warnings,
+        }),
+    ))
+}
+
+fn create_recurring(conn: &Connection, config: &Config, body: &str) -> ApiResult {
+    let body: RecurringBody = parse_body(body)?;
+
+    let entry = add_recurring(conn, config, &body.source, body.amount, body.kind, &body.tag, body.day).map_err(
+        ApiError::bad_request
+    )?;
+    Ok((201, json!(entry)))
+}
+
+fn run_recurring(conn: &Connection, config: &Config, params: &HashMap<String, String>) -> ApiResult {
+    let today = Local::now().date_naive();
+    let until = match params.get("until") {
+        Some(date) => parse_date_bound(date, true).map_err(ApiError::bad_request)?,
+        None => today,
+    };
+
+    if params.get("dry_run").is_some_and(|v| v == "true" || v == "1") {
+        let pending: Vec<Value> = db
+            ::pending_recurring(conn, today, until)?
+            .into_iter()
+            .map(|(entry, due)| json!({ "date": due.format("%Y-%m-%d").to_string(), "entry": entry }))
+            .collect();
+        return Ok((200, json!({ "dry_run": true, "pending": pending })));
+    }
+
+    let posted = db::insert_recurring_until(conn, today, until)?;
+    if !posted.is_empty() {
+        log_hook_errors(hooks::fire(&config.hooks, &Event::RecurringPosted(&posted)));
+    }
+    Ok((200, json!({ "dry_run": false, "posted": posted })))
+}
+
+fn tags_json(config: &Config) -> Value {
+    let budgets = Budgets::from_config(config);
+    let tags: Vec<Value> = config.tags
+        .iter()
+        .map(|name| json!({ "name": name, "budget": budgets.per_tag.get(&Tag::new(name)) }))
+        .collect();
+
+    json!({ "currency": config.currency, "tags": tags })
+}
+
+fn stats(conn: &Connection, config: &Config, params: &HashMap<String, String>) -> ApiResult {
+    let query = Query::parse(params.get("q").map(String::as_str).unwrap_or("")).map_err(ApiError::bad_request)?;
+    let transactions = query.apply(&db::get_transactions(conn)?);
+    let goals = db::get_goals(conn)?;
+    let snapshot = StatsSnapshot::new(&transactions, &Budgets::from_config(config), &goals);
+
+    let per_tag: serde_json::Map<String, Value> = snapshot.per_tag
+        .iter()
+        .map(|(tag, amount)| (tag.as_str().to_string(), json!(cents(*amount))))
+        .collect();
+    let monthly: Vec<Value> = snapshot.monthly_history
+        .iter()
+        .map(|(month, earned, spent)| json!({ "month": month, "earned": cents(*earned), "spent": cents(*spent) }))
+        .collect();
+    let goals: Vec<Value> = snapshot.goals
+        .iter()
+        .map(|g| {
+            json!({
+                "name": g.goal.name,
+                "target": g.goal.target,
+                "target_date": g.goal.target_date,
+                "saved": cents(g.saved),
+                "required_monthly": cents(g.required_monthly),
+            })
+        })
+        .collect();
+
+    let report = &snapshot.budget;
+    Ok((
+        200,
+        json!({
+            "currency": config.currency,
+            "count": snapshot.tx_count,
+            "earned": cents(snapshot.earned),
+            "spent": cents(snapshot.spent),
+            "balance": cents(snapshot.balance),
+            "per_tag": per_tag,
+            "monthly": monthly,
+            "budget": {
+                "month": report.month,
+                "overall": report.overall.as_ref().map(budget_json),
+                "tags": report.tags.iter().map(budget_json).collect::<Vec<_>>(),
+            },
+            "goals": goals,
+        }),
+    ))
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Hook failures don't fail the request; they go to the server log
+fn log_hook_errors(errors: Vec<String>) {
+    for error in errors {
+        eprintln!("warning: {}", error);
+    }
+}
+
+fn budget_json(status: &BudgetStatus) -> Value {
+    json!({
+        "tag": status.tag,
+        "limit": status.limit,
+        "spent": cents(status.spent),
+        "ratio": (status.ratio() * 1000.0).round() / 1000.0,
+    })
+}
+
+fn cents(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+fn find_transaction(conn: &Connection, id: i32) -> Result<Transaction, ApiError> {
+    db::get_transactions(conn)?
+        .into_iter()
+        .find(|tx| tx.id == id)
+        .ok_or_else(|| ApiError::not_found(format!("no transaction with id {}", id)))
+}
+
+fn parse_id(id: &str) -> Result<i32, ApiError> {
+    id.parse().map_err(|_| ApiError::bad_request(format!("invalid id '{}'", id)))
+}
+
+fn parse_body<T: for<'de> Deserialize<'de>>(body: &str) -> Result<T, ApiError> {
+    serde_json::from_str(body).map_err(|e| ApiError::bad_request(format!("invalid JSON body: {}", e)))
+}
+
+fn parse_query_string(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key), percent_decode(value))
+        })
+        .collect()
+}
+
+/// Decode `application/x-www-form-urlencoded` text (`+` is a space)
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                    Some(byte) => {
+                        out.push(byte);
+                        i += 2;
+                    }
+                    None => out.push(b'%'),
+                }
+            }
+            byte => out.push(byte),
+        }
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&out).into_owned()
+}
@@ -0,0 +1,126 @@
+use std::collections::{ BTreeMap, HashMap };
+
+use chrono::{ Datelike, Local, NaiveDate };
+
+use crate::models::{ RecurringEntry, Tag, Transaction, TransactionType };
+
+/// Minimum number of monthly payments before a payee is suggested
+const MIN_OCCURRENCES: usize = 3;
+
+/// Allowed deviation from the latest amount (price changes, taxes, rounding)
+const AMOUNT_TOLERANCE: f64 = 0.15;
+
+/// Range of days between two payments that still counts as "monthly"
+const MONTHLY_GAP_DAYS: std::ops::RangeInclusive<i64> = 25..=35;
+
+/// Payees that haven't charged for longer than this are treated as cancelled
+const MAX_DAYS_SINCE_LAST: i64 = 45;
+
+#[derive(Debug, Clone)]
+pub struct Suggestion {
+    pub source: String,
+    pub amount: f64,
+    pub kind: TransactionType,
+    pub tag: Tag,
+    pub day: u32,
+    pub since: String,      // Format: "YYYY-MM"
+    pub last_month: String, // Format: "YYYY-MM"
+    pub occurrences: usize,
+}
+
+impl Suggestion {
+    pub fn describe(&self, currency: &str) -> String {
+        format!("{}, {}{:.2}, monthly since {}", self.source, currency, self.amount, self.since)
+    }
+}
+
+// ============================================================================
+// Detection functions
+// ============================================================================
+
+/// Scan debit history for payees charging a similar amount every month and
+/// return the ones not already covered by a recurring entry
+pub fn detect_subscriptions(
+    transactions: &[Transaction],
+    recurring_entries: &[RecurringEntry]
+) -> Vec<Suggestion> {
+    let today = Local::now().date_naive();
+
+    let known: Vec<String> = recurring_entries
+        .iter()
+        .map(|e| normalize_source(&e.source))
+        .collect();
+
+    let mut by_source: HashMap<String, Vec<(NaiveDate, &Transaction)>> = HashMap::new();
+    for tx in transactions.iter().filter(|tx| tx.kind == TransactionType::Debit) {
+        if let Ok(date) = NaiveDate::parse_from_str(&tx.date, "%Y-%m-%d") {
+            by_source.entry(normalize_source(&tx.source)).or_default().push((date, tx));
+        }
+    }
+
+    let mut suggestions: Vec<Suggestion> = by_source
+        .into_iter()
+        .filter(|(key, _)| !key.is_empty() && !known.contains(key))
+        .filter_map(|(_, history)| detect_monthly_series(history, today))
+        .collect();
+
+    suggestions.sort_by(|a, b| b.amount.partial_cmp(&a.amount).unwrap_or(std::cmp::Ordering::Equal));
+    suggestions
+}
+
+/// Find a trailing run of monthly payments with a stable amount in a single payee's history
+fn detect_monthly_series(
+    mut history: Vec<(NaiveDate, &Transaction)>,
+    today: NaiveDate
+) -> Option<Suggestion> {
+    history.sort_by_key(|(date, _)| *date);
+
+    let (last_date, latest) = *history.last()?;
+    if (today - last_date).num_days() > MAX_DAYS_SINCE_LAST {
+        return None;
+    }
+
+    // Keep one payment per month, ignoring one-off purchases at the same payee
+    let mut per_month: BTreeMap<(i32, u32), NaiveDate> = BTreeMap::new();
+    for (date, tx) in &history {
+        if amounts_match(tx.amount, latest.amount) {
+            per_month.insert((date.year(), date.month()), *date);
+        }
+    }
+
+    let dates: Vec<NaiveDate> = per_month.into_values().collect();
+
+    // Walk back from the latest payment while the gaps stay monthly
+    let mut start = dates.len() - 1;
+    while start > 0 && MONTHLY_GAP_DAYS.contains(&(dates[start] - dates[start - 1]).num_days()) {
+        start -= 1;
+    }
+
+    let occurrences = dates.len() - start;
+    if occurrences < MIN_OCCURRENCES {
+        return None;
+    }
+
+    Some(Suggestion {
+        source: latest.source.clone(),
+        amount: latest.amount,
+        kind: latest.kind,
+        tag: latest.tag.clone(),
+        day: last_date.day(),
+        since: dates[start].format("%Y-%m").to_string(),
+        last_month: last_date.format("%Y-%m").to_string(),
+        occurrences,
+    })
+}
+
+fn amounts_match(amount: f64, reference: f64) -> bool {
+    if reference == 0.0 {
+        return amount == 0.0;
+    }
+
+    ((amount - reference) / reference).abs() <= AMOUNT_TOLERANCE
+}
+
+fn normalize_source(source: &str) -> String {
+    source.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
@@ -3,7 +3,7 @@ use ratatui::{
     widgets::{Block, Borders},
 };
 
-use crate::models::TransactionType;
+use crate::{budget::WARNING_RATIO, models::TransactionType};
 
 #[derive(Clone, Copy)]
 pub struct Theme {
@@ -12,6 +12,7 @@ pub struct Theme {
 
     pub credit: Color,
     pub debit: Color,
+    pub warning: Color,
 
     pub muted: Color,
     pub subtle: Color,
@@ -22,14 +23,15 @@ pub struct Theme {
     pub foreground: Color,
 }
 
-impl Theme {
-    pub fn default() -> Self {
+impl Default for Theme {
+    fn default() -> Self {
         Self {
             accent: Color::Rgb(100, 181, 246),
             accent_soft: Color::Rgb(80, 140, 200),
 
             credit: Color::Rgb(102, 187, 106),
             debit: Color::Rgb(239, 83, 80),
+            warning: Color::Rgb(255, 183, 77),
 
             muted: Color::Rgb(160, 160, 170),
             subtle: Color::Rgb(90, 90, 110),
@@ -40,7 +42,9 @@ impl Theme {
             foreground: Color::Rgb(220, 225, 245),
         }
     }
+}
 
+impl Theme {
     pub fn transaction_color(&self, tx_type: TransactionType) -> Color {
         match tx_type {
             TransactionType::Credit => self.credit,
@@ -48,6 +52,17 @@ impl Theme {
         }
     }
 
+    /// Green below 80% of the limit, amber up to 100%, red once exceeded
+    pub fn budget_color(&self, ratio: f64) -> Color {
+        if ratio >= 1.0 {
+            self.debit
+        } else if ratio >= WARNING_RATIO {
+            self.warning
+        } else {
+            self.credit
+        }
+    }
+
     pub fn danger(&self) -> Style {
         Style::default()
             .fg(self.debit)
@@ -1,13 +1,26 @@
+pub mod backup;
+pub mod duplicates;
+pub mod envelopes;
+pub mod export;
+pub mod forecast;
+pub mod goals;
+pub mod import;
+pub mod profile;
+pub mod reports;
+pub mod rules;
+pub mod stats;
+pub mod subscriptions;
+
 use ratatui::{
     prelude::*,
     widgets::{Clear, List, ListItem, ListState, Paragraph, Block, Padding},
 };
 
 use crate::{
-    app::{App, Mode},
+    app::{App, Mode, Notification, NotificationLevel},
     form::Field,
     models::{Transaction, TransactionType},
-    stats,
+    quickadd,
     theme::Theme,
     stats::StatsSnapshot,
 };
@@ -23,13 +36,72 @@ pub fn draw_ui(
         Mode::Stats => {
             stats::draw_stats_view(
                 f,
+                app,
                 snapshot,
                 &theme,
-                &app.currency,
             )
         }
 
-        Mode::Adding => {
+        Mode::Forecast => {
+            forecast::draw_forecast_view(f, app, snapshot.balance, &theme)
+        }
+
+        Mode::Suggestions => {
+            subscriptions::draw_suggestions_view(f, app, &theme)
+        }
+
+        Mode::Import => {
+            // The file prompt floats over the main view; review takes the whole screen
+            if let import::ImportStage::SelectFile { .. } = app.import {
+                draw_main_view(
+                    f,
+                    &app.transactions,
+                    snapshot.earned,
+                    snapshot.spent,
+                    snapshot.balance,
+                    app,
+                    &theme,
+                );
+            }
+            import::draw_import_view(f, app, &theme)
+        }
+
+        Mode::Export | Mode::Backup | Mode::Profiles | Mode::QuickAdd => {
+            draw_main_view(
+                f,
+                &app.transactions,
+                snapshot.earned,
+                snapshot.spent,
+                snapshot.balance,
+                app,
+                &theme,
+            );
+
+            match app.mode {
+                Mode::Backup => backup::draw_backup_view(f, app, &theme),
+                Mode::Profiles => profile::draw_profiles_view(f, app, &theme),
+                Mode::QuickAdd => quickadd::draw_quick_add_view(f, app, &theme),
+                _ => export::draw_export_view(f, app, &theme),
+            }
+        }
+
+        Mode::Goals => {
+            goals::draw_goals_view(f, app, &theme)
+        }
+
+        Mode::Envelopes => {
+            envelopes::draw_envelopes_view(f, app, &theme)
+        }
+
+        Mode::Duplicates => {
+            duplicates::draw_duplicates_view(f, app, &theme)
+        }
+
+        Mode::Rules => {
+            rules::draw_rules_view(f, app, &theme)
+        }
+
+        Mode::Adding | Mode::ConfirmOverBudget => {
             draw_main_view(
                 f,
                 &app.transactions,
@@ -41,6 +113,10 @@ pub fn draw_ui(
             );
 
             draw_transaction_form(f, app, &theme);
+
+            if app.mode == Mode::ConfirmOverBudget {
+                draw_budget_confirm(f, app, &theme);
+            }
         }
 
         _ => {
@@ -55,6 +131,41 @@ pub fn draw_ui(
             );
         }
     }
+
+    if let Some(notification) = &app.notification {
+        draw_notification(f, notification, &theme);
+    }
+}
+
+fn draw_notification(f: &mut Frame, notification: &Notification, theme: &Theme) {
+    let color = match notification.level {
+        NotificationLevel::Info => theme.accent,
+        NotificationLevel::Warning => theme.debit,
+    };
+
+    let screen = f.size();
+    let width = (notification.message.chars().count() as u16 + 6).min(screen.width);
+    let area = Rect {
+        x: screen.width.saturating_sub(width + 1),
+        y: 1,
+        width,
+        height: 3.min(screen.height),
+    };
+
+    let toast = Paragraph::new(Line::styled(
+        format!(" {}", notification.message),
+        Style::default().fg(theme.foreground),
+    ))
+    .block(
+        Block::default()
+            .borders(ratatui::widgets::Borders::ALL)
+            .border_set(ratatui::symbols::border::ROUNDED)
+            .border_style(Style::default().fg(color))
+            .style(Style::default().bg(theme.surface)),
+    );
+
+    f.render_widget(Clear, area);
+    f.render_widget(toast, area);
 }
 
 fn draw_main_view(
@@ -169,14 +280,22 @@ fn draw_transactions_list(
 ) {
     let layout = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Min(1), Constraint::Length(3)])
+        .constraints([Constraint::Min(1), Constraint::Length(4)])
         .split(area);
 
-    let items = build_transaction_items(transactions, theme, &app.currency);
+    let highlight = app.highlight.map(|(id, _)| id);
+    let items = build_transaction_items(transactions, theme, &app.currency, highlight);
     let mut state = create_list_state(app.selected);
     
+    // Name the ledger when more than one is in use
+    let title = if app.profile == crate::profile::DEFAULT_PROFILE {
+        " Transactions ".to_string()
+    } else {
+        format!(" Transactions · {} ", app.profile)
+    };
+
     let list = List::new(items)
-        .block(theme.block(" Transactions "))
+        .block(theme.block(&title))
         .highlight_style(theme.highlight_style())
         .highlight_symbol("▶ ");
 
@@ -189,6 +308,7 @@ fn draw_transactions_list(
         .style(Style::default().bg(theme.background))
         .padding(Padding::new(1, 1, 0, 0));
 
+    // Editing, views and data tools on their own lines so none truncates
     let footer_content = vec![
         Line::from(vec![
             Span::styled("  [", theme.muted_text()),
@@ -198,17 +318,54 @@ fn draw_transactions_list(
             Span::styled("a", Style::default().fg(theme.credit)),
             Span::styled("] Add  ", theme.muted_text()),
             Span::styled("[", theme.muted_text()),
+            Span::styled("A", Style::default().fg(theme.credit)),
+            Span::styled("] Quick Add  ", theme.muted_text()),
+            Span::styled("[", theme.muted_text()),
             Span::styled("e", Style::default().fg(theme.accent)),
             Span::styled("] Edit  ", theme.muted_text()),
             Span::styled("[", theme.muted_text()),
             Span::styled("d", Style::default().fg(theme.debit)),
             Span::styled("] Delete  ", theme.muted_text()),
             Span::styled("[", theme.muted_text()),
+            Span::styled("q", Style::default().fg(theme.subtle)),
+            Span::styled("] Quit", theme.muted_text()),
+        ]),
+        Line::from(vec![
+            Span::styled("  [", theme.muted_text()),
             Span::styled("s", Style::default().fg(theme.accent)),
             Span::styled("] Stats  ", theme.muted_text()),
             Span::styled("[", theme.muted_text()),
-            Span::styled("q", Style::default().fg(theme.subtle)),
-            Span::styled("] Quit", theme.muted_text()),
+            Span::styled("f", Style::default().fg(theme.accent)),
+            Span::styled("] Forecast  ", theme.muted_text()),
+            Span::styled("[", theme.muted_text()),
+            Span::styled("v", Style::default().fg(theme.accent)),
+            Span::styled("] Envelopes  ", theme.muted_text()),
+            Span::styled("[", theme.muted_text()),
+            Span::styled("g", Style::default().fg(theme.accent)),
+            Span::styled("] Goals  ", theme.muted_text()),
+            Span::styled("[", theme.muted_text()),
+            Span::styled("r", Style::default().fg(theme.accent)),
+            Span::styled("] Detect Recurring", theme.muted_text()),
+        ]),
+        Line::from(vec![
+            Span::styled("  [", theme.muted_text()),
+            Span::styled("i", Style::default().fg(theme.accent)),
+            Span::styled("] Import  ", theme.muted_text()),
+            Span::styled("[", theme.muted_text()),
+            Span::styled("E", Style::default().fg(theme.accent)),
+            Span::styled("] Export  ", theme.muted_text()),
+            Span::styled("[", theme.muted_text()),
+            Span::styled("B", Style::default().fg(theme.accent)),
+            Span::styled("] Backup  ", theme.muted_text()),
+            Span::styled("[", theme.muted_text()),
+            Span::styled("R", Style::default().fg(theme.accent)),
+            Span::styled("] Rules  ", theme.muted_text()),
+            Span::styled("[", theme.muted_text()),
+            Span::styled("D", Style::default().fg(theme.accent)),
+            Span::styled("] Duplicates  ", theme.muted_text()),
+            Span::styled("[", theme.muted_text()),
+            Span::styled("P", Style::default().fg(theme.accent)),
+            Span::styled("] Profiles", theme.muted_text()),
         ]),
     ];
 
@@ -217,7 +374,12 @@ fn draw_transactions_list(
     f.render_widget(footer, layout[1]);
 }
 
-fn build_transaction_items(transactions: &[Transaction], theme: &Theme, currency: &str) -> Vec<ListItem<'static>> {
+fn build_transaction_items(
+    transactions: &[Transaction],
+    theme: &Theme,
+    currency: &str,
+    highlight: Option<i32>,
+) -> Vec<ListItem<'static>> {
     let mut items = Vec::new();
     
     items.push(create_table_header(theme));
@@ -232,7 +394,7 @@ fn build_transaction_items(transactions: &[Transaction], theme: &Theme, currency
         )));
     } else {
         for tx in transactions {
-            items.push(create_transaction_row(tx, theme, currency));
+            items.push(create_transaction_row(tx, theme, currency, highlight == Some(tx.id)));
         }
     }
     
@@ -256,14 +418,14 @@ fn create_divider(theme: &Theme) -> ListItem<'static> {
     ))
 }
 
-fn create_transaction_row(tx: &Transaction, theme: &Theme, currency: &str) -> ListItem<'static> {
+fn create_transaction_row(tx: &Transaction, theme: &Theme, currency: &str, is_new: bool) -> ListItem<'static> {
     let color = theme.transaction_color(tx.kind);
     let (icon, kind_label) = match tx.kind {
         TransactionType::Credit => ("↑", "Credit"),
         TransactionType::Debit => ("↓", "Debit"),
     };
 
-    let line = Line::from(vec![
+    let mut line = Line::from(vec![
         Span::raw("  "),
         Span::styled(
             format!("{:<11}", tx.date),
@@ -300,6 +462,14 @@ fn create_transaction_row(tx: &Transaction, theme: &Theme, currency: &str) -> Li
         ),
     ]);
 
+    // Added by another process while the TUI was open
+    if is_new {
+        line.spans.push(Span::styled(
+            "  ● new",
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+        ));
+    }
+
     ListItem::new(line)
 }
 
@@ -335,6 +505,38 @@ fn draw_transaction_form(f: &mut Frame, app: &App, theme: &Theme) {
     f.render_widget(popup, area);
 }
 
+fn draw_budget_confirm(f: &mut Frame, app: &App, theme: &Theme) {
+    let area = centered_rect(50, 30, f.size());
+
+    let mut lines = vec![Line::raw("")];
+    for b in &app.pending_breaches {
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled(format!("{:<10}", b.label()), theme.danger()),
+            Span::styled(
+                format!(
+                    "{}{:.2} of {}{:.2}",
+                    app.currency, b.spent, app.currency, b.limit
+                ),
+                Style::default().fg(theme.foreground),
+            ),
+        ]));
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::from(vec![
+        Span::styled("  Save anyway?  [", theme.muted_text()),
+        Span::styled("y", Style::default().fg(theme.credit)),
+        Span::styled("] Yes  [", theme.muted_text()),
+        Span::styled("n", Style::default().fg(theme.debit)),
+        Span::styled("] Back to form", theme.muted_text()),
+    ]));
+
+    let popup = Paragraph::new(lines).block(theme.popup("⚠ Over Budget"));
+
+    f.render_widget(Clear, area);
+    f.render_widget(popup, area);
+}
+
 fn build_form_content(app: &App, theme: &Theme) -> Vec<Line<'static>> {
     let form = &app.form;
     
@@ -0,0 +1,191 @@
+use chrono::Local;
+use crossterm::event::KeyCode;
+use ratatui::{ prelude::*, widgets::{ Clear, Paragraph } };
+use rusqlite::Connection;
+
+use crate::{
+    app::{ App, Mode, NotificationLevel },
+    backup::{ read_backup, restore, write_backup, RestoreMode },
+    config::{ load_config, save_config },
+    import::expand_path,
+    theme::Theme,
+};
+
+// ============================================================================
+// Backup UI rendering functions
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BackupAction {
+    Save,
+    Restore(RestoreMode),
+}
+
+impl BackupAction {
+    pub const ALL: [BackupAction; 3] = [
+        BackupAction::Save,
+        BackupAction::Restore(RestoreMode::Merge),
+        BackupAction::Restore(RestoreMode::Replace),
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            BackupAction::Save => "Save backup",
+            BackupAction::Restore(RestoreMode::Merge) => "Restore (merge)",
+            BackupAction::Restore(RestoreMode::Replace) => "Restore (replace all)",
+        }
+    }
+}
+
+pub struct BackupView {
+    pub path: String,
+    pub action_index: usize,
+    // Replacing wipes the database, so it takes a second Enter
+    pub confirm_replace: bool,
+}
+
+impl Default for BackupView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BackupView {
+    pub fn new() -> Self {
+        Self {
+            path: format!("~/fitui-backup-{}.json", Local::now().format("%Y-%m-%d")),
+            action_index: 0,
+            confirm_replace: false,
+        }
+    }
+
+    pub fn action(&self) -> BackupAction {
+        BackupAction::ALL[self.action_index]
+    }
+}
+
+pub fn draw_backup_view(f: &mut Frame, app: &App, theme: &Theme) {
+    let view = &app.backup;
+
+    let mut action_line = vec![Span::styled("  Action : ", theme.muted_text())];
+    for (i, action) in BackupAction::ALL.iter().enumerate() {
+        let style = if i == view.action_index {
+            Style::default().fg(theme.accent_soft).add_modifier(Modifier::BOLD | Modifier::REVERSED)
+        } else {
+            theme.muted_text()
+        };
+        action_line.push(Span::styled(format!(" {} ", action.label()), style));
+    }
+
+    let hint = if view.confirm_replace {
+        Line::styled("  ⚠ This deletes all current data. Press Enter again to restore.", theme.danger())
+    } else {
+        Line::styled("  Includes transactions, recurring entries, envelopes, goals, tags", theme.muted_text())
+    };
+
+    let lines = vec![
+        Line::raw(""),
+        Line::from(
+            vec![
+                Span::styled("  File   : ", theme.title()),
+                Span::styled(format!("{}│", view.path), Style::default().fg(theme.foreground))
+            ]
+        ),
+        Line::raw(""),
+        Line::from(action_line),
+        Line::styled("           ← →", Style::default().fg(theme.accent)),
+        Line::raw(""),
+        hint,
+        Line::raw(""),
+        Line::styled("  [Enter] Run  [Esc] Cancel", theme.muted_text())
+    ];
+
+    let screen = f.size();
+    let width = 76.min(screen.width);
+    let height = 12.min(screen.height);
+    let area = Rect {
+        x: (screen.width - width) / 2,
+        y: (screen.height - height) / 2,
+        width,
+        height,
+    };
+
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(lines).block(theme.popup("💾 Backup & Restore")), area);
+}
+
+// ============================================================================
+// Backup input handler
+// ============================================================================
+
+pub fn handle_backup(app: &mut App, key: KeyCode, conn: &Connection) -> bool {
+    let count = BackupAction::ALL.len();
+
+    match key {
+        KeyCode::Esc => {
+            app.mode = Mode::Normal;
+        }
+        KeyCode::Char(c) => {
+            app.backup.path.push(c);
+            app.backup.confirm_replace = false;
+        }
+        KeyCode::Backspace => {
+            app.backup.path.pop();
+            app.backup.confirm_replace = false;
+        }
+        KeyCode::Right => {
+            app.backup.action_index = (app.backup.action_index + 1) % count;
+            app.backup.confirm_replace = false;
+        }
+        KeyCode::Left => {
+            app.backup.action_index = (app.backup.action_index + count - 1) % count;
+            app.backup.confirm_replace = false;
+        }
+        KeyCode::Enter => {
+            let path = expand_path(&app.backup.path);
+
+            let result = match app.backup.action() {
+                BackupAction::Save =>
+                    write_backup(conn, &load_config(), &path).map(|count| {
+                        format!("💾 Backed up {} transactions to {}", count, path.display())
+                    }),
+                BackupAction::Restore(RestoreMode::Replace) if !app.backup.confirm_replace => {
+                    app.backup.confirm_replace = true;
+                    return false;
+                }
+                BackupAction::Restore(mode) => {
+                    let mut config = load_config();
+                    read_backup(&path)
+                        .and_then(|backup| restore(conn, &backup, mode, &mut config))
+                        .and_then(|summary| {
+                            if summary.config_changed {
+                                save_config(&config)?;
+                            }
+                            // Hook failures ride along with the summary rather than replace it
+                            let mut message = summary.describe();
+                            for warning in &summary.warnings {
+                                message.push_str(&format!("; {}", warning));
+                            }
+                            Ok(message)
+                        })
+                }
+            };
+
+            match result {
+                Ok(message) => {
+                    app.mode = Mode::Normal;
+                    app.reload_config();
+                    app.refresh(conn);
+                    app.notify(message, NotificationLevel::Info);
+                }
+                Err(e) => {
+                    app.backup.confirm_replace = false;
+                    app.notify(e, NotificationLevel::Warning);
+                }
+            }
+        }
+        _ => {}
+    }
+
+    false
+}
@@ -0,0 +1,176 @@
+use crossterm::event::KeyCode;
+use ratatui::{ prelude::*, widgets::{ Block, Paragraph } };
+use rusqlite::Connection;
+
+use crate::{ app::{ App, Mode }, duplicates::Resolution, models::Transaction, theme::Theme };
+
+// ============================================================================
+// Duplicates UI rendering functions
+// ============================================================================
+
+pub fn draw_duplicates_view(f: &mut Frame, app: &App, theme: &Theme) {
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Length(3), Constraint::Min(1), Constraint::Length(3)])
+        .split(f.size());
+
+    let Some(pair) = app.duplicates.get(app.duplicate_selected) else {
+        let empty = Paragraph::new(
+            Line::styled(
+                "  No likely duplicates found.",
+                Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC)
+            )
+        ).block(theme.block(" Duplicates "));
+        f.render_widget(empty, layout[1]);
+        draw_footer(f, layout[2], theme);
+        return;
+    };
+
+    let header = Paragraph::new(
+        Line::from(
+            vec![
+                Span::styled(
+                    format!(" Pair {} of {} ", app.duplicate_selected + 1, app.duplicates.len()),
+                    theme.title()
+                ),
+                Span::styled(
+                    format!(" · sources {:.0}% similar", pair.similarity * 100.0),
+                    theme.muted_text()
+                )
+            ]
+        )
+    ).block(theme.panel());
+    f.render_widget(header, layout[0]);
+
+    let sides = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(layout[1]);
+
+    draw_side(f, sides[0], " 1 · Keep ", &pair.first, &pair.second, app, theme);
+    draw_side(f, sides[1], " 2 · Duplicate ", &pair.second, &pair.first, app, theme);
+
+    draw_footer(f, layout[2], theme);
+}
+
+fn draw_side(
+    f: &mut Frame,
+    area: Rect,
+    title: &str,
+    tx: &Transaction,
+    other: &Transaction,
+    app: &App,
+    theme: &Theme
+) {
+    // Fields that differ from the other side are highlighted
+    let value = |text: String, same: bool| {
+        let style = if same {
+            Style::default().fg(theme.foreground)
+        } else {
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+        };
+        Span::styled(text, style)
+    };
+
+    let lines = vec![
+        Line::raw(""),
+        Line::from(
+            vec![
+                Span::styled("  Source : ", theme.muted_text()),
+                value(tx.source.clone(), tx.source == other.source)
+            ]
+        ),
+        Line::from(
+            vec![
+                Span::styled("  Amount : ", theme.muted_text()),
+                Span::styled(
+                    format!("{}{:.2}", app.currency, tx.amount),
+                    Style::default().fg(theme.transaction_color(tx.kind))
+                )
+            ]
+        ),
+        Line::from(
+            vec![
+                Span::styled("  Date   : ", theme.muted_text()),
+                value(tx.date.clone(), tx.date == other.date)
+            ]
+        ),
+        Line::from(
+            vec![
+                Span::styled("  Tag    : ", theme.muted_text()),
+                value(format!("#{}", tx.tag.as_str()), tx.tag == other.tag)
+            ]
+        ),
+        Line::from(
+            vec![
+                Span::styled("  Notes  : ", theme.muted_text()),
+                value(tx.notes.clone(), tx.notes == other.notes)
+            ]
+        ),
+        Line::from(
+            vec![
+                Span::styled("  Id     : ", theme.muted_text()),
+                Span::styled(tx.id.to_string(), theme.muted_text())
+            ]
+        )
+    ];
+
+    f.render_widget(Paragraph::new(lines).block(theme.block(title)), area);
+}
+
+fn draw_footer(f: &mut Frame, area: Rect, theme: &Theme) {
+    let footer_block = Block::default()
+        .borders(ratatui::widgets::Borders::TOP)
+        .border_style(Style::default().fg(theme.subtle))
+        .style(Style::default().bg(theme.background))
+        .padding(ratatui::widgets::Padding::new(1, 1, 0, 0));
+
+    let footer = Paragraph::new(
+        Line::from(
+            vec![
+                Span::styled("  [", theme.muted_text()),
+                Span::styled("←→", Style::default().fg(theme.accent)),
+                Span::styled("] Pairs  ", theme.muted_text()),
+                Span::styled("[", theme.muted_text()),
+                Span::styled("m", Style::default().fg(theme.credit)),
+                Span::styled("] Merge into 1  ", theme.muted_text()),
+                Span::styled("[", theme.muted_text()),
+                Span::styled("k", Style::default().fg(theme.accent)),
+                Span::styled("] Keep Both  ", theme.muted_text()),
+                Span::styled("[", theme.muted_text()),
+                Span::styled("1/2", Style::default().fg(theme.debit)),
+                Span::styled("] Delete  ", theme.muted_text()),
+                Span::styled("[", theme.muted_text()),
+                Span::styled("Esc", Style::default().fg(theme.subtle)),
+                Span::styled("] Back", theme.muted_text())
+            ]
+        )
+    ).block(footer_block);
+    f.render_widget(footer, area);
+}
+
+// ============================================================================
+// Duplicates input handler
+// ============================================================================
+
+pub fn handle_duplicates(app: &mut App, key: KeyCode, conn: &Connection) -> bool {
+    match key {
+        KeyCode::Esc => {
+            app.mode = Mode::Normal;
+        }
+        KeyCode::Left | KeyCode::Up if app.duplicate_selected > 0 => {
+            app.duplicate_selected -= 1;
+        }
+        KeyCode::Right | KeyCode::Down if app.duplicate_selected + 1 < app.duplicates.len() => {
+            app.duplicate_selected += 1;
+        }
+        KeyCode::Char('m') => app.resolve_duplicate(conn, Resolution::Merge),
+        KeyCode::Char('k') => app.resolve_duplicate(conn, Resolution::KeepBoth),
+        KeyCode::Char('1') => app.resolve_duplicate(conn, Resolution::DeleteFirst),
+        KeyCode::Char('2') => app.resolve_duplicate(conn, Resolution::DeleteSecond),
+        _ => {}
+    }
+
+    false
+}
@@ -0,0 +1,482 @@
+use chrono::Local;
+use crossterm::event::KeyCode;
+use ratatui::{ prelude::*, widgets::{ Block, Clear, List, ListItem, ListState, Paragraph } };
+use rusqlite::Connection;
+
+use crate::{
+    app::{ App, Mode, NotificationLevel },
+    db,
+    envelopes::{ envelope_ledger, ready_to_assign, shift_month },
+    models::{ Envelope, Tag, TransactionType },
+    theme::Theme,
+};
+
+// ============================================================================
+// Envelopes view state
+// ============================================================================
+
+/// Pending text input inside the envelopes view
+pub enum EnvelopeInput {
+    None,
+    NewEnvelope {
+        name: String,
+        tag_index: usize,
+    },
+    Assign(String),
+}
+
+/// Navigation state of the envelopes view
+pub struct EnvelopeView {
+    pub month: String, // Format: "YYYY-MM"
+    pub selected: usize,
+    pub input: EnvelopeInput,
+}
+
+impl Default for EnvelopeView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EnvelopeView {
+    pub fn new() -> Self {
+        Self {
+            month: Local::now().format("%Y-%m").to_string(),
+            selected: 0,
+            input: EnvelopeInput::None,
+        }
+    }
+}
+
+// ============================================================================
+// Envelopes UI rendering functions
+// ============================================================================
+
+pub fn draw_envelopes_view(f: &mut Frame, app: &App, theme: &Theme) {
+    let view = &app.envelope_view;
+    let currency = &app.currency;
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(5),
+            Constraint::Min(5),
+            Constraint::Length(10),
+            Constraint::Length(3),
+        ])
+        .split(f.size());
+
+    // Header: month, income and money still waiting for an envelope
+    let month_income: f64 = app.transactions
+        .iter()
+        .filter(|tx| tx.kind == TransactionType::Credit && tx.date.starts_with(&view.month))
+        .map(|tx| tx.amount)
+        .sum();
+    let to_assign = ready_to_assign(&app.envelope_assignments, &app.transactions, &view.month);
+
+    let header = Paragraph::new(
+        vec![
+            Line::from(
+                vec![
+                    Span::styled("◀ ", Style::default().fg(theme.accent)),
+                    Span::styled(view.month.clone(), theme.title()),
+                    Span::styled(" ▶", Style::default().fg(theme.accent))
+                ]
+            ),
+            Line::from(
+                vec![
+                    Span::styled("Income this month: ", theme.muted_text()),
+                    Span::styled(format!("{}{:.2}", currency, month_income), theme.success()),
+                    Span::raw("    "),
+                    Span::styled("Ready to assign: ", theme.muted_text()),
+                    Span::styled(
+                        format!("{}{:.2}", currency, to_assign),
+                        if to_assign < 0.0 { theme.danger() } else { theme.success() }
+                    )
+                ]
+            )
+        ]
+    )
+        .block(theme.panel())
+        .alignment(Alignment::Center);
+    f.render_widget(header, layout[0]);
+
+    // Envelope table for the selected month
+    let mut items = vec![
+        ListItem::new(
+            Line::styled(
+                format!(
+                    "  {:<16} {:<12} {:>12} {:>12} {:>12} {:>12}",
+                    "Envelope",
+                    "Tag",
+                    "Carried",
+                    "Assigned",
+                    "Spent",
+                    "Available"
+                ),
+                Style::default().fg(theme.muted).add_modifier(Modifier::BOLD)
+            )
+        )
+    ];
+
+    if app.envelopes.is_empty() {
+        items.push(
+            ListItem::new(
+                Line::styled(
+                    "  No envelopes yet. Press 'n' to create one!",
+                    Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC)
+                )
+            )
+        );
+    }
+
+    for envelope in &app.envelopes {
+        let ledger = envelope_ledger(
+            envelope,
+            &app.envelope_assignments,
+            &app.transactions,
+            &view.month
+        );
+        let Some(current) = ledger.last() else {
+            continue;
+        };
+
+        let available_color = if current.available < 0.0 { theme.debit } else { theme.credit };
+        items.push(
+            ListItem::new(
+                Line::from(
+                    vec![
+                        Span::raw("  "),
+                        Span::styled(
+                            format!("{:<16}", envelope.name),
+                            Style::default().fg(theme.foreground)
+                        ),
+                        Span::raw(" "),
+                        Span::styled(
+                            format!("{:<12}", format!("#{}", envelope.tag.as_str())),
+                            Style::default().fg(theme.accent_soft).add_modifier(Modifier::ITALIC)
+                        ),
+                        Span::styled(
+                            format!(" {:>12.2}", current.carried_in),
+                            theme.muted_text()
+                        ),
+                        Span::styled(
+                            format!(" {:>12.2}", current.assigned),
+                            Style::default().fg(theme.accent)
+                        ),
+                        Span::styled(
+                            format!(" {:>12.2}", current.spent),
+                            Style::default().fg(theme.debit)
+                        ),
+                        Span::styled(
+                            format!(" {:>12.2}", current.available),
+                            Style::default().fg(available_color).add_modifier(Modifier::BOLD)
+                        )
+                    ]
+                )
+            )
+        );
+    }
+
+    let mut state = ListState::default();
+    if !app.envelopes.is_empty() {
+        state.select(Some(view.selected + 1));
+    }
+
+    let list = List::new(items)
+        .block(theme.block(" Envelopes "))
+        .highlight_style(theme.highlight_style())
+        .highlight_symbol("▶ ");
+    f.render_stateful_widget(list, layout[1], &mut state);
+
+    // Ledger of the selected envelope
+    let ledger_lines = match app.envelopes.get(view.selected) {
+        Some(envelope) => build_ledger_lines(envelope, app, theme),
+        None => vec![],
+    };
+    let ledger = Paragraph::new(ledger_lines).block(theme.block(" Ledger "));
+    f.render_widget(ledger, layout[2]);
+
+    let footer_block = Block::default()
+        .borders(ratatui::widgets::Borders::TOP)
+        .border_style(Style::default().fg(theme.subtle))
+        .style(Style::default().bg(theme.background))
+        .padding(ratatui::widgets::Padding::new(1, 1, 0, 0));
+
+    let footer = Paragraph::new(
+        Line::from(
+            vec![
+                Span::styled("  [", theme.muted_text()),
+                Span::styled("↑↓", Style::default().fg(theme.accent)),
+                Span::styled("] Select  ", theme.muted_text()),
+                Span::styled("[", theme.muted_text()),
+                Span::styled("←→", Style::default().fg(theme.accent)),
+                Span::styled("] Month  ", theme.muted_text()),
+                Span::styled("[", theme.muted_text()),
+                Span::styled("Enter", Style::default().fg(theme.credit)),
+                Span::styled("] Assign  ", theme.muted_text()),
+                Span::styled("[", theme.muted_text()),
+                Span::styled("n", Style::default().fg(theme.credit)),
+                Span::styled("] New  ", theme.muted_text()),
+                Span::styled("[", theme.muted_text()),
+                Span::styled("d", Style::default().fg(theme.debit)),
+                Span::styled("] Delete  ", theme.muted_text()),
+                Span::styled("[", theme.muted_text()),
+                Span::styled("Esc", Style::default().fg(theme.subtle)),
+                Span::styled("] Back", theme.muted_text())
+            ]
+        )
+    ).block(footer_block);
+    f.render_widget(footer, layout[3]);
+
+    draw_envelope_input(f, app, theme);
+}
+
+fn build_ledger_lines(envelope: &Envelope, app: &App, theme: &Theme) -> Vec<Line<'static>> {
+    let ledger = envelope_ledger(
+        envelope,
+        &app.envelope_assignments,
+        &app.transactions,
+        &app.envelope_view.month
+    );
+
+    let mut lines = vec![
+        Line::styled(
+            format!(
+                "  {:<9} {:>12} {:>12} {:>12} {:>12}",
+                "Month",
+                "Carried",
+                "Assigned",
+                "Spent",
+                "Available"
+            ),
+            Style::default().fg(theme.muted).add_modifier(Modifier::BOLD)
+        )
+    ];
+
+    // Most recent months first; the panel only has room for a few
+    for entry in ledger.iter().rev().take(7) {
+        let available_color = if entry.available < 0.0 { theme.debit } else { theme.credit };
+        lines.push(
+            Line::from(
+                vec![
+                    Span::styled(format!("  {:<9}", entry.month), Style::default().fg(theme.foreground)),
+                    Span::styled(format!(" {:>12.2}", entry.carried_in), theme.muted_text()),
+                    Span::styled(format!(" {:>12.2}", entry.assigned), Style::default().fg(theme.accent)),
+                    Span::styled(format!(" {:>12.2}", entry.spent), Style::default().fg(theme.debit)),
+                    Span::styled(
+                        format!(" {:>12.2}", entry.available),
+                        Style::default().fg(available_color)
+                    )
+                ]
+            )
+        );
+    }
+
+    lines
+}
+
+fn draw_envelope_input(f: &mut Frame, app: &App, theme: &Theme) {
+    let (title, lines) = match &app.envelope_view.input {
+        EnvelopeInput::None => {
+            return;
+        }
+        EnvelopeInput::NewEnvelope { name, tag_index } => {
+            let tag = app.tags
+                .get(*tag_index)
+                .map(|t| t.as_str().to_string())
+                .unwrap_or_else(|| "other".into());
+            (
+                "➕ New Envelope",
+                vec![
+                    Line::raw(""),
+                    Line::from(
+                        vec![
+                            Span::styled("  Name    : ", theme.title()),
+                            Span::styled(format!("{}│", name), Style::default().fg(theme.foreground))
+                        ]
+                    ),
+                    Line::from(
+                        vec![
+                            Span::styled("  Tag     : ", theme.muted_text()),
+                            Span::styled(
+                                format!("#{}", tag),
+                                Style::default().fg(theme.accent_soft).add_modifier(Modifier::ITALIC)
+                            ),
+                            Span::styled("  ← →", Style::default().fg(theme.accent))
+                        ]
+                    ),
+                    Line::raw(""),
+                    Line::styled("  [Enter] Create  [Esc] Cancel", theme.muted_text())
+                ],
+            )
+        }
+        EnvelopeInput::Assign(amount) => {
+            let name = app.envelopes
+                .get(app.envelope_view.selected)
+                .map(|e| e.name.clone())
+                .unwrap_or_default();
+            (
+                "💰 Assign Money",
+                vec![
+                    Line::raw(""),
+                    Line::styled(
+                        format!("  {} for {}", name, app.envelope_view.month),
+                        theme.muted_text()
+                    ),
+                    Line::from(
+                        vec![
+                            Span::styled("  Amount  : ", theme.title()),
+                            Span::styled(
+                                format!("{}{}│", app.currency, amount),
+                                Style::default().fg(theme.foreground)
+                            )
+                        ]
+                    ),
+                    Line::raw(""),
+                    Line::styled("  [Enter] Save  [Esc] Cancel", theme.muted_text())
+                ],
+            )
+        }
+    };
+
+    let screen = f.size();
+    let width = 50.min(screen.width);
+    let height = 8.min(screen.height);
+    let area = Rect {
+        x: (screen.width - width) / 2,
+        y: (screen.height - height) / 2,
+        width,
+        height,
+    };
+
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(lines).block(theme.popup(title)), area);
+}
+
+// ============================================================================
+// Envelopes input handler
+// ============================================================================
+
+pub fn handle_envelopes(app: &mut App, key: KeyCode, conn: &Connection) -> bool {
+    match &mut app.envelope_view.input {
+        EnvelopeInput::None => {}
+        EnvelopeInput::NewEnvelope { name, tag_index } => {
+            match key {
+                KeyCode::Esc => {
+                    app.envelope_view.input = EnvelopeInput::None;
+                }
+                KeyCode::Char(c) => name.push(c),
+                KeyCode::Backspace => {
+                    name.pop();
+                }
+                KeyCode::Right if !app.tags.is_empty() => {
+                    *tag_index = (*tag_index + 1) % app.tags.len();
+                }
+                KeyCode::Left if !app.tags.is_empty() => {
+                    *tag_index = (*tag_index + app.tags.len() - 1) % app.tags.len();
+                }
+                KeyCode::Enter => {
+                    let name = name.trim().to_string();
+                    let tag = app.tags.get(*tag_index).cloned().unwrap_or(Tag("other".into()));
+                    app.envelope_view.input = EnvelopeInput::None;
+
+                    if !name.is_empty() {
+                        if let Err(e) = db::add_envelope(conn, &name, &tag) {
+                            app.notify(
+                                format!("Could not create envelope: {}", e),
+                                NotificationLevel::Warning
+                            );
+                        }
+                        app.refresh(conn);
+                    }
+                }
+                _ => {}
+            }
+            return false;
+        }
+        EnvelopeInput::Assign(amount) => {
+            match key {
+                KeyCode::Esc => {
+                    app.envelope_view.input = EnvelopeInput::None;
+                }
+                KeyCode::Char(c) => amount.push(c),
+                KeyCode::Backspace => {
+                    amount.pop();
+                }
+                KeyCode::Enter => {
+                    // Keep the prompt open rather than overwrite the assignment with 0
+                    let Some(value) = amount
+                        .trim()
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|v| v.is_finite() && *v >= 0.0) else {
+                        let message = format!("'{}' is not an amount, e.g. 250 or 99.50", amount.trim());
+                        app.notify(message, NotificationLevel::Warning);
+                        return false;
+                    };
+                    app.envelope_view.input = EnvelopeInput::None;
+
+                    if let Some(envelope) = app.envelopes.get(app.envelope_view.selected) {
+                        db::set_envelope_assignment(
+                            conn,
+                            envelope.id,
+                            &app.envelope_view.month,
+                            value
+                        ).unwrap();
+                        app.refresh(conn);
+                    }
+                }
+                _ => {}
+            }
+            return false;
+        }
+    }
+
+    let view = &mut app.envelope_view;
+
+    match key {
+        KeyCode::Esc => {
+            app.mode = Mode::Normal;
+        }
+        KeyCode::Up if view.selected > 0 => {
+            view.selected -= 1;
+        }
+        KeyCode::Down if view.selected + 1 < app.envelopes.len() => {
+            view.selected += 1;
+        }
+        KeyCode::Left => {
+            view.month = shift_month(&view.month, -1);
+        }
+        KeyCode::Right => {
+            view.month = shift_month(&view.month, 1);
+        }
+        KeyCode::Char('n') => {
+            view.input = EnvelopeInput::NewEnvelope {
+                name: String::new(),
+                tag_index: 0,
+            };
+        }
+        KeyCode::Enter if !app.envelopes.is_empty() => {
+            // Pre-fill with the current assignment so it can be adjusted
+            let current = app.envelope_assignments
+                .iter()
+                .find(|a| a.envelope_id == app.envelopes[view.selected].id && a.month == view.month)
+                .map(|a| format!("{:.2}", a.amount))
+                .unwrap_or_default();
+            view.input = EnvelopeInput::Assign(current);
+        }
+        KeyCode::Char('d') if !app.envelopes.is_empty() => {
+            let id = app.envelopes[view.selected].id;
+            if view.selected > 0 && view.selected + 1 >= app.envelopes.len() {
+                view.selected -= 1;
+            }
+            db::delete_envelope(conn, id).unwrap();
+            app.refresh(conn);
+        }
+        _ => {}
+    }
+
+    false
+}
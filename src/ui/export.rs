@@ -0,0 +1,327 @@
+use std::fs;
+
+use chrono::NaiveDate;
+use crossterm::event::KeyCode;
+use ratatui::{ prelude::*, widgets::{ Clear, Paragraph } };
+
+use crate::{
+    app::{ App, Mode, NotificationLevel },
+    export::{ render, ExportContext, ExportFormat },
+    filter::{ parse_date_bound, TransactionFilter },
+    import::expand_path,
+    models::{ Tag, TransactionType },
+    theme::Theme,
+};
+
+// ============================================================================
+// Export form state
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportField {
+    Path,
+    Format,
+    From,
+    To,
+    Tag,
+    Kind,
+    Search,
+}
+
+impl ExportField {
+    const ORDER: [ExportField; 7] = [
+        ExportField::Path,
+        ExportField::Format,
+        ExportField::From,
+        ExportField::To,
+        ExportField::Tag,
+        ExportField::Kind,
+        ExportField::Search,
+    ];
+
+    fn step(self, delta: isize) -> Self {
+        let count = Self::ORDER.len() as isize;
+        let index = Self::ORDER.iter()
+            .position(|f| *f == self)
+            .unwrap_or(0) as isize;
+        Self::ORDER[(index + delta).rem_euclid(count) as usize]
+    }
+}
+
+pub struct ExportView {
+    pub path: String,
+    pub format_index: usize,
+    pub field: ExportField,
+    pub from: String,
+    pub to: String,
+    // None exports every tag
+    pub tag_index: Option<usize>,
+    pub kind: Option<TransactionType>,
+    pub search: String,
+}
+
+impl Default for ExportView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExportView {
+    pub fn new() -> Self {
+        Self {
+            path: ExportFormat::ALL[0].default_path(),
+            format_index: 0,
+            field: ExportField::Path,
+            from: String::new(),
+            to: String::new(),
+            tag_index: None,
+            kind: None,
+            search: String::new(),
+        }
+    }
+
+    pub fn format(&self) -> ExportFormat {
+        ExportFormat::ALL[self.format_index]
+    }
+
+    /// Switch format, following along with the file name if it wasn't edited
+    fn cycle_format(&mut self, delta: isize) {
+        let previous = self.format().default_path();
+        let count = ExportFormat::ALL.len() as isize;
+        self.format_index = ((self.format_index as isize) + delta).rem_euclid(count) as usize;
+
+        if self.path == previous {
+            self.path = self.format().default_path();
+        }
+    }
+
+    /// Cycle through "all tags" followed by each configured tag
+    fn cycle_tag(&mut self, delta: isize, tag_count: usize) {
+        let slots = (tag_count + 1) as isize;
+        let current = self.tag_index.map(|i| i + 1).unwrap_or(0) as isize;
+        let next = (current + delta).rem_euclid(slots) as usize;
+        self.tag_index = next.checked_sub(1);
+    }
+
+    fn cycle_kind(&mut self) {
+        self.kind = match self.kind {
+            None => Some(TransactionType::Debit),
+            Some(TransactionType::Debit) => Some(TransactionType::Credit),
+            Some(TransactionType::Credit) => None,
+        };
+    }
+
+    fn text_mut(&mut self) -> Option<&mut String> {
+        match self.field {
+            ExportField::Path => Some(&mut self.path),
+            ExportField::From => Some(&mut self.from),
+            ExportField::To => Some(&mut self.to),
+            ExportField::Search => Some(&mut self.search),
+            _ => None,
+        }
+    }
+
+    pub fn filter(&self, tags: &[Tag]) -> Result<TransactionFilter, String> {
+        let bound = |text: &str, end: bool| -> Result<Option<NaiveDate>, String> {
+            if text.trim().is_empty() { Ok(None) } else { parse_date_bound(text, end).map(Some) }
+        };
+
+        Ok(TransactionFilter {
+            from: bound(&self.from, false)?,
+            to: bound(&self.to, true)?,
+            tags: self.tag_index
+                .and_then(|i| tags.get(i))
+                .cloned()
+                .into_iter()
+                .collect(),
+            kind: self.kind,
+            search: Some(self.search.trim().to_string()).filter(|s| !s.is_empty()),
+        })
+    }
+}
+
+// ============================================================================
+// Export UI rendering functions
+// ============================================================================
+
+pub fn draw_export_view(f: &mut Frame, app: &App, theme: &Theme) {
+    let view = &app.export;
+
+    let label = |field: ExportField, text: &'static str| {
+        if view.field == field {
+            Span::styled(text, theme.title())
+        } else {
+            Span::styled(text, theme.muted_text())
+        }
+    };
+    let text = |value: &str, field: ExportField, placeholder: &str| {
+        if view.field == field {
+            Span::styled(format!("{}│", value), Style::default().fg(theme.foreground))
+        } else if value.is_empty() {
+            Span::styled(placeholder.to_string(), Style::default().fg(theme.subtle))
+        } else {
+            Span::styled(value.to_string(), Style::default().fg(theme.foreground))
+        }
+    };
+    let choice = |value: String, field: ExportField| {
+        let style = Style::default().fg(theme.accent_soft).add_modifier(Modifier::BOLD);
+        if view.field == field {
+            Span::styled(format!("‹ {} ›", value), style)
+        } else {
+            Span::styled(value, style)
+        }
+    };
+
+    let tag = view.tag_index
+        .and_then(|i| app.tags.get(i))
+        .map(|t| format!("#{}", t.as_str()))
+        .unwrap_or_else(|| "all tags".into());
+    let kind = view.kind.map(|k| k.as_str().to_string()).unwrap_or_else(|| "all".into());
+
+    let matching = match view.filter(&app.tags) {
+        Ok(filter) => {
+            let count = app.transactions
+                .iter()
+                .filter(|tx| filter.matches(tx))
+                .count();
+            Line::styled(
+                format!("  {} of {} transactions selected", count, app.transactions.len()),
+                theme.muted_text()
+            )
+        }
+        Err(e) => Line::styled(format!("  {}", e), theme.danger()),
+    };
+
+    let lines = vec![
+        Line::raw(""),
+        Line::from(
+            vec![
+                label(ExportField::Path, "  File   : "),
+                text(&view.path, ExportField::Path, "")
+            ]
+        ),
+        Line::from(
+            vec![
+                label(ExportField::Format, "  Format : "),
+                choice(view.format().name().into(), ExportField::Format)
+            ]
+        ),
+        Line::raw(""),
+        Line::from(
+            vec![
+                label(ExportField::From, "  From   : "),
+                text(&view.from, ExportField::From, "any (YYYY, YYYY-MM or YYYY-MM-DD)")
+            ]
+        ),
+        Line::from(
+            vec![
+                label(ExportField::To, "  To     : "),
+                text(&view.to, ExportField::To, "any")
+            ]
+        ),
+        Line::from(
+            vec![
+                label(ExportField::Tag, "  Tag    : "),
+                choice(tag, ExportField::Tag)
+            ]
+        ),
+        Line::from(
+            vec![
+                label(ExportField::Kind, "  Type   : "),
+                choice(kind, ExportField::Kind)
+            ]
+        ),
+        Line::from(
+            vec![
+                label(ExportField::Search, "  Search : "),
+                text(&view.search, ExportField::Search, "source or notes")
+            ]
+        ),
+        Line::raw(""),
+        matching,
+        Line::raw(""),
+        Line::styled("  [Tab/↑↓] Field  [←→] Change  [Enter] Export  [Esc] Cancel", theme.muted_text())
+    ];
+
+    let screen = f.size();
+    let width = 72.min(screen.width);
+    let height = 16.min(screen.height);
+    let area = Rect {
+        x: (screen.width - width) / 2,
+        y: (screen.height - height) / 2,
+        width,
+        height,
+    };
+
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(lines).block(theme.popup("📤 Export")), area);
+}
+
+// ============================================================================
+// Export input handler
+// ============================================================================
+
+pub fn handle_export(app: &mut App, key: KeyCode) -> bool {
+    let tag_count = app.tags.len();
+    let view = &mut app.export;
+
+    match key {
+        KeyCode::Esc => {
+            app.mode = Mode::Normal;
+        }
+        KeyCode::Tab | KeyCode::Down => {
+            view.field = view.field.step(1);
+        }
+        KeyCode::BackTab | KeyCode::Up => {
+            view.field = view.field.step(-1);
+        }
+        KeyCode::Right | KeyCode::Left => {
+            let delta = if key == KeyCode::Right { 1 } else { -1 };
+            match view.field {
+                ExportField::Format => view.cycle_format(delta),
+                ExportField::Tag => view.cycle_tag(delta, tag_count),
+                ExportField::Kind => view.cycle_kind(),
+                _ => {}
+            }
+        }
+        KeyCode::Char(c) => {
+            if let Some(text) = view.text_mut() {
+                text.push(c);
+            }
+        }
+        KeyCode::Backspace => {
+            if let Some(text) = view.text_mut() {
+                text.pop();
+            }
+        }
+        KeyCode::Enter => {
+            match write_export(app) {
+                Ok(message) => {
+                    app.mode = Mode::Normal;
+                    app.notify(message, NotificationLevel::Info);
+                }
+                Err(e) => app.notify(e, NotificationLevel::Warning),
+            }
+        }
+        _ => {}
+    }
+
+    false
+}
+
+fn write_export(app: &App) -> Result<String, String> {
+    let view = &app.export;
+    let selected = view.filter(&app.tags)?.apply(&app.transactions);
+    let context = ExportContext {
+        recurring: &app.recurring_entries,
+        currency: &app.currency,
+        csv: &app.csv_export,
+        budgets: &app.budgets,
+    };
+    let content = render(view.format(), &selected, &context)?;
+
+    let path = expand_path(&view.path);
+    fs::write(&path, content).map_err(|e| format!("Could not write {}: {}", path.display(), e))?;
+
+    Ok(format!("📤 Exported {} transactions to {}", selected.len(), path.display()))
+}
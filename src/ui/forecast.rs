@@ -0,0 +1,254 @@
+use chrono::Local;
+use crossterm::event::KeyCode;
+use ratatui::{
+    prelude::*,
+    symbols::Marker,
+    widgets::{ Axis, Block, Chart, Dataset, GraphType, Paragraph },
+};
+
+use crate::{ app::{ App, Mode }, forecast::{ Forecast, HORIZONS }, theme::Theme };
+
+// ============================================================================
+// Forecast UI rendering functions
+// ============================================================================
+
+pub fn draw_forecast_view(f: &mut Frame, app: &App, balance: f64, theme: &Theme) {
+    let today = Local::now().date_naive();
+    let forecast = Forecast::new(balance, &app.recurring_entries, today, app.forecast_days);
+    let currency = &app.currency;
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(1), Constraint::Length(11), Constraint::Length(3)])
+        .split(f.size());
+
+    draw_forecast_chart(f, layout[0], &forecast, app.forecast_days, theme, currency);
+
+    let details = Paragraph::new(build_forecast_summary(&forecast, theme, currency))
+        .block(theme.block("Summary"))
+        .alignment(Alignment::Left);
+    f.render_widget(details, layout[1]);
+
+    let footer_block = Block::default()
+        .borders(ratatui::widgets::Borders::TOP)
+        .border_style(Style::default().fg(theme.subtle))
+        .style(Style::default().bg(theme.background))
+        .padding(ratatui::widgets::Padding::new(1, 1, 0, 0));
+
+    let footer = Paragraph::new(
+        Line::from(
+            vec![
+                Span::styled("  [", theme.muted_text()),
+                Span::styled("←→", Style::default().fg(theme.accent)),
+                Span::styled("] Horizon (30/90/365 days)  ", theme.muted_text()),
+                Span::styled("[", theme.muted_text()),
+                Span::styled("Esc", Style::default().fg(theme.debit)),
+                Span::styled("] Back to Main View", theme.muted_text())
+            ]
+        )
+    ).block(footer_block);
+
+    f.render_widget(footer, layout[2]);
+}
+
+fn draw_forecast_chart(
+    f: &mut Frame,
+    area: Rect,
+    forecast: &Forecast,
+    days: i64,
+    theme: &Theme,
+    currency: &str
+) {
+    let data: Vec<(f64, f64)> = forecast.points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (i as f64, p.balance))
+        .collect();
+
+    let lowest: Vec<(f64, f64)> = forecast
+        .lowest_point()
+        .map(|p| vec![(forecast.lowest as f64, p.balance)])
+        .unwrap_or_default();
+
+    let x_max = (days - 1).max(1) as f64;
+    let zero_line = vec![(0.0, 0.0), (x_max, 0.0)];
+
+    let (mut y_min, mut y_max) = data
+        .iter()
+        .fold((0.0_f64, 0.0_f64), |(lo, hi), (_, y)| (lo.min(*y), hi.max(*y)));
+    let padding = ((y_max - y_min) * 0.1).max(1.0);
+    y_min -= padding;
+    y_max += padding;
+
+    let line_color = if forecast.first_negative.is_some() { theme.debit } else { theme.credit };
+
+    let datasets = vec![
+        Dataset::default()
+            .name("zero")
+            .marker(Marker::Dot)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(theme.subtle))
+            .data(&zero_line),
+        Dataset::default()
+            .name("balance")
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(line_color))
+            .data(&data),
+        Dataset::default()
+            .name("lowest")
+            .marker(Marker::Block)
+            .graph_type(GraphType::Scatter)
+            .style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD))
+            .data(&lowest)
+    ];
+
+    let first_label = forecast.points
+        .first()
+        .map(|p| p.date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    let mid_label = forecast.points
+        .get(forecast.points.len() / 2)
+        .map(|p| p.date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    let last_label = forecast.points
+        .last()
+        .map(|p| p.date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+
+    let title = format!(" Projected Balance – next {} days ", days);
+
+    let chart = Chart::new(datasets)
+        .block(theme.block(&title))
+        .x_axis(
+            Axis::default()
+                .style(theme.muted_text())
+                .bounds([0.0, x_max])
+                .labels(vec![Span::raw(first_label), Span::raw(mid_label), Span::raw(last_label)])
+        )
+        .y_axis(
+            Axis::default()
+                .style(theme.muted_text())
+                .bounds([y_min, y_max])
+                .labels(
+                    vec![
+                        Span::raw(format!("{}{:.0}", currency, y_min)),
+                        Span::raw(format!("{}{:.0}", currency, (y_min + y_max) / 2.0)),
+                        Span::raw(format!("{}{:.0}", currency, y_max))
+                    ]
+                )
+        );
+
+    f.render_widget(chart, area);
+}
+
+fn build_forecast_summary(forecast: &Forecast, theme: &Theme, currency: &str) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+
+    if let Some(low) = forecast.lowest_point() {
+        let low_color = if low.balance < 0.0 { theme.debit } else { theme.accent };
+        lines.push(
+            Line::from(
+                vec![
+                    Span::raw("  Lowest point  : "),
+                    Span::styled(
+                        format!("{}{:.2}", currency, low.balance),
+                        Style::default().fg(low_color).add_modifier(Modifier::BOLD)
+                    ),
+                    Span::styled(format!("  on {}", low.date.format("%Y-%m-%d")), theme.muted_text())
+                ]
+            )
+        );
+    }
+
+    lines.push(
+        Line::from(
+            vec![
+                Span::raw("  Final balance : "),
+                Span::styled(
+                    format!("{}{:.2}", currency, forecast.final_balance()),
+                    Style::default().fg(theme.foreground).add_modifier(Modifier::BOLD)
+                )
+            ]
+        )
+    );
+
+    match forecast.first_negative {
+        Some(date) =>
+            lines.push(
+                Line::styled(
+                    format!("  ⚠ Balance goes negative on {}", date.format("%Y-%m-%d")),
+                    theme.danger()
+                )
+            ),
+        None => lines.push(Line::styled("  ✓ Balance stays positive", theme.success())),
+    }
+
+    lines.push(Line::raw(""));
+    lines.push(
+        Line::styled(
+            "  Upcoming Recurring",
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+        )
+    );
+
+    if forecast.upcoming.is_empty() {
+        lines.push(
+            Line::styled(
+                "     No active recurring entries.",
+                Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC)
+            )
+        );
+    } else {
+        for (date, entry) in forecast.upcoming.iter().take(4) {
+            let color = theme.transaction_color(entry.kind);
+            lines.push(
+                Line::from(
+                    vec![
+                        Span::raw("     "),
+                        Span::styled(
+                            format!("{:<11}", date.format("%Y-%m-%d")),
+                            Style::default().fg(theme.muted)
+                        ),
+                        Span::styled(
+                            format!("{:<16}", entry.source),
+                            Style::default().fg(theme.foreground)
+                        ),
+                        Span::styled(
+                            format!("{}{:>9.2}", currency, entry.amount),
+                            Style::default().fg(color)
+                        )
+                    ]
+                )
+            );
+        }
+    }
+
+    lines
+}
+
+// ============================================================================
+// Forecast input handler
+// ============================================================================
+
+pub fn handle_forecast(app: &mut App, key: KeyCode) -> bool {
+    let current = HORIZONS.iter()
+        .position(|d| *d == app.forecast_days)
+        .unwrap_or(0);
+
+    match key {
+        KeyCode::Esc => {
+            app.mode = Mode::Normal;
+        }
+        KeyCode::Right => {
+            app.forecast_days = HORIZONS[(current + 1) % HORIZONS.len()];
+        }
+        KeyCode::Left => {
+            app.forecast_days = HORIZONS[(current + HORIZONS.len() - 1) % HORIZONS.len()];
+        }
+        _ => {}
+    }
+
+    false
+}
@@ -0,0 +1,441 @@
+use chrono::NaiveDate;
+use crossterm::event::KeyCode;
+use ratatui::{ prelude::*, widgets::{ Block, Clear, Gauge, Paragraph } };
+use rusqlite::Connection;
+
+use crate::{
+    app::{ App, Mode, NotificationLevel },
+    db,
+    goals::{ calculate_goal_progress, GoalProgress },
+    models::Tag,
+    theme::Theme,
+};
+
+// ============================================================================
+// Goals in the stats view
+// ============================================================================
+
+fn goal_color(progress: &GoalProgress, theme: &Theme) -> Color {
+    if progress.is_complete() {
+        theme.credit
+    } else if progress.on_track() {
+        theme.accent
+    } else {
+        theme.warning
+    }
+}
+
+/// Short text gauges for the stats view
+pub fn create_goals_section(
+    goals: &[GoalProgress],
+    theme: &Theme,
+    currency: &str
+) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::styled(
+            "  🏁 Savings Goals",
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+        ),
+        Line::raw("")
+    ];
+
+    for progress in goals {
+        let filled = (progress.ratio() * 20.0).round() as usize;
+        let color = goal_color(progress, theme);
+
+        lines.push(
+            Line::from(
+                vec![
+                    Span::raw("     "),
+                    Span::styled(
+                        format!("{:<14}", progress.goal.name),
+                        Style::default().fg(theme.foreground)
+                    ),
+                    Span::styled("█".repeat(filled), Style::default().fg(color)),
+                    Span::styled("░".repeat(20 - filled), Style::default().fg(theme.subtle)),
+                    Span::styled(
+                        format!(
+                            "  {:>5.1}%  {}{:.2}/month needed",
+                            progress.ratio() * 100.0,
+                            currency,
+                            progress.required_monthly
+                        ),
+                        theme.muted_text()
+                    )
+                ]
+            )
+        );
+    }
+
+    lines
+}
+
+// ============================================================================
+// Goal form
+// ============================================================================
+
+#[derive(PartialEq, Copy, Clone)]
+pub enum GoalField {
+    Name,
+    Target,
+    Date,
+    Tag,
+}
+
+impl GoalField {
+    pub fn next(self) -> Self {
+        match self {
+            GoalField::Name => GoalField::Target,
+            GoalField::Target => GoalField::Date,
+            GoalField::Date => GoalField::Tag,
+            GoalField::Tag => GoalField::Name,
+        }
+    }
+}
+
+pub struct GoalForm {
+    pub name: String,
+    pub target: String,
+    pub date: String,
+    pub tag_index: usize,
+    pub active: GoalField,
+}
+
+impl Default for GoalForm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GoalForm {
+    pub fn new() -> Self {
+        Self {
+            name: String::new(),
+            target: String::new(),
+            date: String::new(),
+            tag_index: 0,
+            active: GoalField::Name,
+        }
+    }
+}
+
+/// Navigation state of the goals screen
+pub struct GoalView {
+    pub selected: usize,
+    pub form: Option<GoalForm>,
+}
+
+impl Default for GoalView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GoalView {
+    pub fn new() -> Self {
+        Self {
+            selected: 0,
+            form: None,
+        }
+    }
+}
+
+// ============================================================================
+// Goals UI rendering functions
+// ============================================================================
+
+pub fn draw_goals_view(f: &mut Frame, app: &App, theme: &Theme) {
+    let currency = &app.currency;
+    let progress = calculate_goal_progress(&app.goals, &app.transactions);
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(1), Constraint::Length(3)])
+        .split(f.size());
+
+    let block = theme.block(" Savings Goals ");
+    let inner = block.inner(layout[0]);
+    f.render_widget(block, layout[0]);
+
+    if progress.is_empty() {
+        f.render_widget(
+            Paragraph::new(
+                Line::styled(
+                    "  No goals yet. Press 'n' to add one!",
+                    Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC)
+                )
+            ),
+            inner
+        );
+    }
+
+    // Each goal takes a title line, a gauge and a details line
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            progress
+                .iter()
+                .map(|_| Constraint::Length(4))
+                .chain(std::iter::once(Constraint::Min(0)))
+                .collect::<Vec<_>>()
+        )
+        .split(inner);
+
+    for (i, p) in progress.iter().enumerate() {
+        let parts = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Length(1), Constraint::Length(1)])
+            .split(rows[i]);
+
+        let selected = i == app.goal_view.selected;
+        let marker = if selected { "▶ " } else { "  " };
+        let name_style = if selected {
+            theme.highlight_style()
+        } else {
+            Style::default().fg(theme.foreground).add_modifier(Modifier::BOLD)
+        };
+
+        f.render_widget(
+            Paragraph::new(
+                Line::from(
+                    vec![
+                        Span::styled(marker, Style::default().fg(theme.accent)),
+                        Span::styled(p.goal.name.clone(), name_style),
+                        Span::raw("  "),
+                        Span::styled(
+                            format!("#{}", p.goal.tag.as_str()),
+                            Style::default().fg(theme.accent_soft).add_modifier(Modifier::ITALIC)
+                        ),
+                        Span::styled(format!("  by {}", p.goal.target_date), theme.muted_text())
+                    ]
+                )
+            ),
+            parts[0]
+        );
+
+        let gauge = Gauge::default()
+            .gauge_style(Style::default().fg(goal_color(p, theme)).bg(theme.surface))
+            .ratio(p.ratio())
+            .label(
+                format!(
+                    "{}{:.2} / {}{:.2} ({:.1}%)",
+                    currency,
+                    p.saved,
+                    currency,
+                    p.goal.target,
+                    p.ratio() * 100.0
+                )
+            );
+        f.render_widget(gauge, parts[1]);
+
+        let status = if p.is_complete() {
+            Span::styled("✓ Reached", theme.success())
+        } else if p.on_track() {
+            Span::styled("On track", Style::default().fg(theme.accent))
+        } else {
+            Span::styled("Behind", Style::default().fg(theme.warning).add_modifier(Modifier::BOLD))
+        };
+
+        f.render_widget(
+            Paragraph::new(
+                Line::from(
+                    vec![
+                        Span::styled(
+                            format!(
+                                "  Need {}{:.2}/month for {:.1} months · recently {}{:.2}/month  ",
+                                currency,
+                                p.required_monthly,
+                                p.months_left,
+                                currency,
+                                p.recent_monthly
+                            ),
+                            theme.muted_text()
+                        ),
+                        status
+                    ]
+                )
+            ),
+            parts[2]
+        );
+    }
+
+    let footer_block = Block::default()
+        .borders(ratatui::widgets::Borders::TOP)
+        .border_style(Style::default().fg(theme.subtle))
+        .style(Style::default().bg(theme.background))
+        .padding(ratatui::widgets::Padding::new(1, 1, 0, 0));
+
+    let footer = Paragraph::new(
+        Line::from(
+            vec![
+                Span::styled("  [", theme.muted_text()),
+                Span::styled("↑↓", Style::default().fg(theme.accent)),
+                Span::styled("] Select  ", theme.muted_text()),
+                Span::styled("[", theme.muted_text()),
+                Span::styled("n", Style::default().fg(theme.credit)),
+                Span::styled("] New Goal  ", theme.muted_text()),
+                Span::styled("[", theme.muted_text()),
+                Span::styled("d", Style::default().fg(theme.debit)),
+                Span::styled("] Delete  ", theme.muted_text()),
+                Span::styled("[", theme.muted_text()),
+                Span::styled("Esc", Style::default().fg(theme.subtle)),
+                Span::styled("] Back", theme.muted_text())
+            ]
+        )
+    ).block(footer_block);
+    f.render_widget(footer, layout[1]);
+
+    if let Some(form) = &app.goal_view.form {
+        draw_goal_form(f, form, &app.tags, theme);
+    }
+}
+
+fn draw_goal_form(f: &mut Frame, form: &GoalForm, tags: &[Tag], theme: &Theme) {
+    let field = |label: &str, value: String, field: GoalField, placeholder: &str| {
+        let is_active = form.active == field;
+        let label_style = if is_active { theme.title() } else { theme.muted_text() };
+        let (text, value_style) = if value.is_empty() && !is_active {
+            (placeholder.to_string(), Style::default().fg(theme.subtle).add_modifier(Modifier::ITALIC))
+        } else {
+            (
+                format!("{}{}", value, if is_active { "│" } else { "" }),
+                Style::default().fg(theme.foreground),
+            )
+        };
+
+        Line::from(
+            vec![
+                Span::raw("  "),
+                Span::styled(format!("{:<8}", label), label_style),
+                Span::raw(": "),
+                Span::styled(text, value_style)
+            ]
+        )
+    };
+
+    let tag = tags
+        .get(form.tag_index)
+        .map(|t| format!("#{}  ← →", t.as_str()))
+        .unwrap_or_else(|| "#other".into());
+
+    let lines = vec![
+        Line::raw(""),
+        field("Name", form.name.clone(), GoalField::Name, "e.g., Emergency fund"),
+        Line::raw(""),
+        field("Target", form.target.clone(), GoalField::Target, "e.g., 100000"),
+        Line::raw(""),
+        field("By", form.date.clone(), GoalField::Date, "YYYY-MM-DD"),
+        Line::raw(""),
+        field("Tag", tag, GoalField::Tag, ""),
+        Line::raw(""),
+        Line::styled("  [Tab] Next Field  [Enter] Save  [Esc] Cancel", theme.muted_text())
+    ];
+
+    let screen = f.size();
+    let width = 56.min(screen.width);
+    let height = 13.min(screen.height);
+    let area = Rect {
+        x: (screen.width - width) / 2,
+        y: (screen.height - height) / 2,
+        width,
+        height,
+    };
+
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(lines).block(theme.popup("🏁 New Savings Goal")), area);
+}
+
+// ============================================================================
+// Goals input handler
+// ============================================================================
+
+pub fn handle_goals(app: &mut App, key: KeyCode, conn: &Connection) -> bool {
+    if let Some(form) = &mut app.goal_view.form {
+        match key {
+            KeyCode::Esc => {
+                app.goal_view.form = None;
+            }
+            KeyCode::Tab => {
+                form.active = form.active.next();
+            }
+            KeyCode::Right if form.active == GoalField::Tag && !app.tags.is_empty() => {
+                form.tag_index = (form.tag_index + 1) % app.tags.len();
+            }
+            KeyCode::Left if form.active == GoalField::Tag && !app.tags.is_empty() => {
+                form.tag_index = (form.tag_index + app.tags.len() - 1) % app.tags.len();
+            }
+            KeyCode::Char(c) =>
+                match form.active {
+                    GoalField::Name => form.name.push(c),
+                    GoalField::Target => form.target.push(c),
+                    GoalField::Date => form.date.push(c),
+                    GoalField::Tag => {}
+                }
+            KeyCode::Backspace => {
+                match form.active {
+                    GoalField::Name => form.name.pop(),
+                    GoalField::Target => form.target.pop(),
+                    GoalField::Date => form.date.pop(),
+                    GoalField::Tag => None,
+                };
+            }
+            KeyCode::Enter => {
+                // Keep the form open rather than save a typo as a target of 0
+                let Some(target) = form.target
+                    .trim()
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|v| v.is_finite() && *v > 0.0) else {
+                    let message = format!("'{}' is not a target amount, e.g. 5000 or 1200.50", form.target.trim());
+                    app.notify(message, NotificationLevel::Warning);
+                    return false;
+                };
+                let valid_date = NaiveDate::parse_from_str(form.date.trim(), "%Y-%m-%d").is_ok();
+
+                if form.name.trim().is_empty() || !valid_date {
+                    app.notify(
+                        "A goal needs a name and a YYYY-MM-DD date".into(),
+                        NotificationLevel::Warning
+                    );
+                    return false;
+                }
+
+                let tag = app.tags.get(form.tag_index).cloned().unwrap_or(Tag("other".into()));
+                db::add_goal(conn, form.name.trim(), target, form.date.trim(), &tag).unwrap();
+
+                app.goal_view.form = None;
+                app.refresh(conn);
+            }
+            _ => {}
+        }
+
+        return false;
+    }
+
+    match key {
+        KeyCode::Esc => {
+            app.mode = Mode::Normal;
+        }
+        KeyCode::Up if app.goal_view.selected > 0 => {
+            app.goal_view.selected -= 1;
+        }
+        KeyCode::Down if app.goal_view.selected + 1 < app.goals.len() => {
+            app.goal_view.selected += 1;
+        }
+        KeyCode::Char('n') => {
+            app.goal_view.form = Some(GoalForm::new());
+        }
+        KeyCode::Char('d') if !app.goals.is_empty() => {
+            let id = app.goals[app.goal_view.selected].id;
+            if app.goal_view.selected > 0 && app.goal_view.selected + 1 >= app.goals.len() {
+                app.goal_view.selected -= 1;
+            }
+            db::delete_goal(conn, id).unwrap();
+            app.refresh(conn);
+        }
+        _ => {}
+    }
+
+    false
+}
@@ -0,0 +1,330 @@
+use crossterm::event::KeyCode;
+use ratatui::{ prelude::*, widgets::{ Block, Clear, List, ListItem, ListState, Paragraph } };
+use rusqlite::Connection;
+
+use crate::{
+    app::{ App, Mode, NotificationLevel },
+    duplicates,
+    import::{ commit_rows, expand_path, load_file, ImportSession },
+    theme::Theme,
+};
+
+// ============================================================================
+// Import view state
+// ============================================================================
+
+pub enum ImportStage {
+    SelectFile {
+        path: String,
+        profile_index: usize,
+    },
+    Review(ImportSession),
+}
+
+impl Default for ImportStage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ImportStage {
+    pub fn new() -> Self {
+        ImportStage::SelectFile {
+            path: String::new(),
+            profile_index: 0,
+        }
+    }
+}
+
+// ============================================================================
+// Import UI rendering functions
+// ============================================================================
+
+pub fn draw_import_view(f: &mut Frame, app: &App, theme: &Theme) {
+    match &app.import {
+        ImportStage::SelectFile { path, profile_index } => {
+            draw_file_prompt(f, app, path, *profile_index, theme)
+        }
+        ImportStage::Review(session) => draw_review(f, app, session, theme),
+    }
+}
+
+fn draw_file_prompt(f: &mut Frame, app: &App, path: &str, profile_index: usize, theme: &Theme) {
+    let profile = app.import_profiles
+        .get(profile_index)
+        .map(|(name, _)| name.clone())
+        .unwrap_or_else(|| "(none configured)".into());
+
+    let lines = vec![
+        Line::raw(""),
+        Line::from(
+            vec![
+                Span::styled("  File    : ", theme.title()),
+                Span::styled(format!("{}│", path), Style::default().fg(theme.foreground))
+            ]
+        ),
+        Line::raw(""),
+        Line::from(
+            vec![
+                Span::styled("  Profile : ", theme.muted_text()),
+                Span::styled(profile, Style::default().fg(theme.accent_soft).add_modifier(Modifier::BOLD)),
+                Span::styled("  ← →", Style::default().fg(theme.accent))
+            ]
+        ),
+        Line::raw(""),
+        Line::styled("  Profiles are defined under import_profiles in config.yaml", theme.muted_text()),
+        Line::styled("  OFX/QFX statements and .journal files are detected automatically", theme.muted_text()),
+        Line::raw(""),
+        Line::styled("  [Enter] Preview  [Esc] Cancel", theme.muted_text())
+    ];
+
+    let screen = f.size();
+    let width = 70.min(screen.width);
+    let height = 12.min(screen.height);
+    let area = Rect {
+        x: (screen.width - width) / 2,
+        y: (screen.height - height) / 2,
+        width,
+        height,
+    };
+
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(lines).block(theme.popup("📥 Import Transactions")), area);
+}
+
+fn draw_review(f: &mut Frame, app: &App, session: &ImportSession, theme: &Theme) {
+    let currency = &app.currency;
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(if session.errors.is_empty() { 3 } else { 5 }),
+            Constraint::Min(1),
+            Constraint::Length(3),
+        ])
+        .split(f.size());
+
+    let mut summary = vec![
+        Line::from(
+            vec![
+                Span::styled(format!(" {} ", session.path.display()), theme.title()),
+                Span::styled(
+                    format!(
+                        " {} rows · {} selected · {} skipped",
+                        session.rows.len(),
+                        session.selected_count(),
+                        session.rows.len() - session.selected_count()
+                    ),
+                    theme.muted_text()
+                )
+            ]
+        )
+    ];
+    if !session.errors.is_empty() {
+        summary.push(
+            Line::styled(format!(" {} lines could not be read:", session.errors.len()), theme.danger())
+        );
+        summary.push(Line::styled(format!("   {}", session.errors[0]), theme.muted_text()));
+    }
+    f.render_widget(Paragraph::new(summary).block(theme.panel()), layout[0]);
+
+    let items: Vec<ListItem> = session.rows
+        .iter()
+        .map(|row| {
+            let color = theme.transaction_color(row.kind);
+            let base = if row.skip {
+                Style::default().fg(theme.subtle).add_modifier(Modifier::CROSSED_OUT)
+            } else {
+                Style::default().fg(theme.foreground)
+            };
+            let mark = if row.skip { "✗" } else { "✓" };
+
+            ListItem::new(
+                Line::from(
+                    vec![
+                        Span::styled(
+                            format!(" {} ", mark),
+                            if row.skip { theme.danger() } else { theme.success() }
+                        ),
+                        Span::styled(format!("{:<11}", row.date), base.fg(theme.muted)),
+                        Span::styled(format!("{:<28}", truncate(&row.source, 27)), base),
+                        Span::styled(
+                            format!("{}{:>10.2}", currency, row.amount),
+                            if row.skip { base } else { Style::default().fg(color) }
+                        ),
+                        Span::raw("  "),
+                        Span::styled(
+                            format!("#{}", row.tag.as_str()),
+                            Style::default().fg(theme.accent_soft).add_modifier(Modifier::ITALIC)
+                        ),
+                        Span::styled(
+                            row.note
+                                .as_ref()
+                                .map(|n| format!("  ({})", n))
+                                .unwrap_or_default(),
+                            theme.muted_text()
+                        )
+                    ]
+                )
+            )
+        })
+        .collect();
+
+    let mut state = ListState::default();
+    if !session.rows.is_empty() {
+        state.select(Some(session.selected));
+    }
+
+    let list = List::new(items)
+        .block(theme.block(" Review Import "))
+        .highlight_style(theme.highlight_style())
+        .highlight_symbol("▶ ");
+    f.render_stateful_widget(list, layout[1], &mut state);
+
+    let footer_block = Block::default()
+        .borders(ratatui::widgets::Borders::TOP)
+        .border_style(Style::default().fg(theme.subtle))
+        .style(Style::default().bg(theme.background))
+        .padding(ratatui::widgets::Padding::new(1, 1, 0, 0));
+
+    let footer = Paragraph::new(
+        Line::from(
+            vec![
+                Span::styled("  [", theme.muted_text()),
+                Span::styled("↑↓", Style::default().fg(theme.accent)),
+                Span::styled("] Navigate  ", theme.muted_text()),
+                Span::styled("[", theme.muted_text()),
+                Span::styled("Space", Style::default().fg(theme.accent)),
+                Span::styled("] Skip/Include  ", theme.muted_text()),
+                Span::styled("[", theme.muted_text()),
+                Span::styled("←→", Style::default().fg(theme.accent)),
+                Span::styled("] Retag  ", theme.muted_text()),
+                Span::styled("[", theme.muted_text()),
+                Span::styled("Enter", Style::default().fg(theme.credit)),
+                Span::styled("] Import  ", theme.muted_text()),
+                Span::styled("[", theme.muted_text()),
+                Span::styled("Esc", Style::default().fg(theme.debit)),
+                Span::styled("] Cancel", theme.muted_text())
+            ]
+        )
+    ).block(footer_block);
+    f.render_widget(footer, layout[2]);
+}
+
+fn truncate(s: &str, max_chars: usize) -> String {
+    if s.chars().count() <= max_chars {
+        s.to_string()
+    } else {
+        format!("{}…", s.chars().take(max_chars - 1).collect::<String>())
+    }
+}
+
+// ============================================================================
+// Import input handler
+// ============================================================================
+
+pub fn handle_import(app: &mut App, key: KeyCode, conn: &Connection) -> bool {
+    match &mut app.import {
+        ImportStage::SelectFile { path, profile_index } => {
+            let profiles = app.import_profiles.len();
+            match key {
+                KeyCode::Esc => {
+                    app.mode = Mode::Normal;
+                }
+                KeyCode::Char(c) => path.push(c),
+                KeyCode::Backspace => {
+                    path.pop();
+                }
+                KeyCode::Right if profiles > 0 => {
+                    *profile_index = (*profile_index + 1) % profiles;
+                }
+                KeyCode::Left if profiles > 0 => {
+                    *profile_index = (*profile_index + profiles - 1) % profiles;
+                }
+                KeyCode::Enter => {
+                    let file = expand_path(path);
+                    let profile = app.import_profiles.get(*profile_index).map(|(_, p)| p);
+                    let result = load_file(
+                        conn,
+                        file,
+                        profile,
+                        &app.ofx_accounts,
+                        &app.rules,
+                        &app.tags
+                    );
+
+                    match result {
+                        Ok(mut session) => {
+                            duplicates::mark_import_duplicates(
+                                &mut session.rows,
+                                &app.transactions,
+                                &app.duplicate_tolerance
+                            );
+                            app.import = ImportStage::Review(session);
+                        }
+                        Err(e) => app.notify(e, NotificationLevel::Warning),
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        ImportStage::Review(session) => {
+            let tag_count = app.tags.len();
+            match key {
+                KeyCode::Esc => {
+                    app.import = ImportStage::new();
+                    app.mode = Mode::Normal;
+                }
+                KeyCode::Up if session.selected > 0 => {
+                    session.selected -= 1;
+                }
+                KeyCode::Down if session.selected + 1 < session.rows.len() => {
+                    session.selected += 1;
+                }
+                KeyCode::Char(' ') | KeyCode::Char('x') => {
+                    if let Some(row) = session.rows.get_mut(session.selected) {
+                        row.skip = !row.skip;
+                    }
+                }
+                KeyCode::Right | KeyCode::Left if tag_count > 0 => {
+                    if let Some(row) = session.rows.get_mut(session.selected) {
+                        let current = app.tags
+                            .iter()
+                            .position(|t| *t == row.tag)
+                            .unwrap_or(0);
+                        let next = if key == KeyCode::Right {
+                            (current + 1) % tag_count
+                        } else {
+                            (current + tag_count - 1) % tag_count
+                        };
+                        row.tag = app.tags[next].clone();
+                    }
+                }
+                KeyCode::Enter => {
+                    match commit_rows(conn, &session.rows, &app.hooks) {
+                        Ok(imported) => {
+                            app.import = ImportStage::new();
+                            app.mode = Mode::Normal;
+                            app.refresh(conn);
+                            app.notify(
+                                format!("📥 Imported {} transactions", imported.value),
+                                NotificationLevel::Info
+                            );
+                            app.report_hook_errors(&imported.warnings);
+                        }
+                        Err(e) =>
+                            app.notify(
+                                format!("Import failed, nothing was saved: {}", e),
+                                NotificationLevel::Warning
+                            ),
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    false
+}
@@ -0,0 +1,98 @@
+use crossterm::event::KeyCode;
+use ratatui::{ prelude::*, widgets::{ Clear, List, ListItem, ListState } };
+
+use crate::{ app::{ App, Mode }, theme::Theme };
+
+// ============================================================================
+// Profile switcher UI rendering functions
+// ============================================================================
+
+pub fn draw_profiles_view(f: &mut Frame, app: &App, theme: &Theme) {
+    let mut items: Vec<ListItem> = app.profiles
+        .iter()
+        .map(|name| {
+            let current = *name == app.profile;
+            ListItem::new(
+                Line::from(
+                    vec![
+                        Span::raw("  "),
+                        Span::styled(name.clone(), Style::default().fg(theme.foreground)),
+                        Span::styled(if current { "  (open)" } else { "" }, theme.muted_text())
+                    ]
+                )
+            )
+        })
+        .collect();
+
+    items.push(
+        ListItem::new(
+            Line::from(
+                vec![
+                    Span::raw("  "),
+                    Span::styled("+ New: ", theme.muted_text()),
+                    Span::styled(format!("{}│", app.new_profile), Style::default().fg(theme.foreground))
+                ]
+            )
+        )
+    );
+
+    let mut state = ListState::default();
+    state.select(Some(app.profile_selected));
+
+    let list = List::new(items)
+        .block(theme.popup("👤 Profiles  [Enter] Open  [Esc] Cancel"))
+        .highlight_style(theme.highlight_style())
+        .highlight_symbol("▶ ");
+
+    let screen = f.size();
+    let width = 56.min(screen.width);
+    let height = ((app.profiles.len() as u16) + 3).min(screen.height);
+    let area = Rect {
+        x: (screen.width - width) / 2,
+        y: (screen.height - height) / 2,
+        width,
+        height,
+    };
+
+    f.render_widget(Clear, area);
+    f.render_stateful_widget(list, area, &mut state);
+}
+
+// ============================================================================
+// Profile switcher input handler
+// ============================================================================
+
+/// The last row is a text field for creating a profile; the actual switch
+/// happens in the event loop, which owns the database connection
+pub fn handle_profiles(app: &mut App, key: KeyCode) -> bool {
+    let on_new = app.profile_selected == app.profiles.len();
+
+    match key {
+        KeyCode::Esc => {
+            app.mode = Mode::Normal;
+        }
+        KeyCode::Up if app.profile_selected > 0 => {
+            app.profile_selected -= 1;
+        }
+        KeyCode::Down if !on_new => {
+            app.profile_selected += 1;
+        }
+        KeyCode::Char(c) if on_new => {
+            app.new_profile.push(c);
+        }
+        KeyCode::Backspace if on_new => {
+            app.new_profile.pop();
+        }
+        KeyCode::Enter => {
+            let name = if on_new { app.new_profile.trim().to_string() } else { app.profiles[app.profile_selected].clone() };
+
+            if !name.is_empty() && name != app.profile {
+                app.switch_profile = Some(name);
+            }
+            app.mode = Mode::Normal;
+        }
+        _ => {}
+    }
+
+    false
+}
@@ -0,0 +1,65 @@
+use ratatui::{ prelude::*, widgets::{ Paragraph, Row, Table, Wrap } };
+
+use crate::{ reports::{ Report, ReportOutput }, theme::Theme };
+
+// ============================================================================
+// Report UI rendering functions
+// ============================================================================
+
+pub fn draw_report(f: &mut Frame, area: Rect, report: &Report, theme: &Theme) {
+    let title = format!(" 📜 {} ", report.name);
+    let block = theme.block(&title);
+
+    match &report.output {
+        ReportOutput::Text(text) => {
+            let paragraph = Paragraph::new(text.clone())
+                .style(Style::default().fg(theme.foreground))
+                .block(block)
+                .wrap(Wrap { trim: false });
+            f.render_widget(paragraph, area);
+        }
+        ReportOutput::Error(message) => {
+            let paragraph = Paragraph::new(message.clone())
+                .style(theme.danger())
+                .block(block)
+                .wrap(Wrap { trim: false });
+            f.render_widget(paragraph, area);
+        }
+        ReportOutput::Table { text, headers, rows } => {
+            let inner = block.inner(area);
+            f.render_widget(block, area);
+
+            let text_height = if text.is_empty() { 0 } else { (text.lines().count() as u16) + 1 };
+            let parts = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(text_height), Constraint::Min(1)])
+                .split(inner);
+
+            f.render_widget(Paragraph::new(text.clone()).style(Style::default().fg(theme.foreground)), parts[0]);
+
+            let columns = headers.len().max(rows.iter().map(|r| r.len()).max().unwrap_or(0));
+            let widths: Vec<Constraint> = (0..columns)
+                .map(|i| {
+                    let width = rows
+                        .iter()
+                        .filter_map(|r| r.get(i))
+                        .chain(headers.get(i))
+                        .map(|c| c.chars().count())
+                        .max()
+                        .unwrap_or(0);
+                    Constraint::Length(width as u16)
+                })
+                .collect();
+
+            let table = Table::new(
+                rows.iter().map(|r| Row::new(r.clone())),
+                widths
+            )
+                .header(Row::new(headers.clone()).style(Style::default().fg(theme.muted).add_modifier(Modifier::BOLD)))
+                .column_spacing(2)
+                .style(Style::default().fg(theme.foreground));
+
+            f.render_widget(table, parts[1]);
+        }
+    }
+}
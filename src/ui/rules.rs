@@ -0,0 +1,152 @@
+use crossterm::event::KeyCode;
+use ratatui::{ prelude::*, widgets::{ Block, List, ListItem, ListState, Paragraph } };
+use rusqlite::Connection;
+
+use crate::{ app::{ App, Mode, NotificationLevel }, rules::apply_changes, theme::Theme };
+
+// ============================================================================
+// Rules UI rendering functions
+// ============================================================================
+
+pub fn draw_rules_view(f: &mut Frame, app: &App, theme: &Theme) {
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(1), Constraint::Length(3)])
+        .split(f.size());
+
+    let mut items: Vec<ListItem> = Vec::new();
+
+    if app.rule_changes.is_empty() {
+        items.push(
+            ListItem::new(
+                Line::styled(
+                    "  Rules don't change any existing transaction.",
+                    Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC)
+                )
+            )
+        );
+    }
+
+    for change in &app.rule_changes {
+        let tx = &change.before;
+        let mut lines = vec![
+            Line::from(
+                vec![
+                    Span::styled(format!("  {:<11}", tx.date), theme.muted_text()),
+                    Span::styled(
+                        format!("{}{:.2}", app.currency, tx.amount),
+                        Style::default().fg(theme.transaction_color(tx.kind))
+                    ),
+                    Span::styled(format!("  rule {}", change.rule), theme.muted_text())
+                ]
+            )
+        ];
+
+        if change.source != tx.source {
+            lines.push(diff_line("source", &tx.source, &change.source, theme));
+        }
+        if change.tag != tx.tag {
+            lines.push(
+                diff_line(
+                    "tag",
+                    &format!("#{}", tx.tag.as_str()),
+                    &format!("#{}", change.tag.as_str()),
+                    theme
+                )
+            );
+        }
+        if change.notes != tx.notes {
+            lines.push(diff_line("notes", &tx.notes, &change.notes, theme));
+        }
+
+        items.push(ListItem::new(lines));
+    }
+
+    let mut state = ListState::default();
+    if !app.rule_changes.is_empty() {
+        state.select(Some(app.rule_selected));
+    }
+
+    let title = format!(" Rules Dry Run – {} changes ", app.rule_changes.len());
+    let list = List::new(items)
+        .block(theme.block(&title))
+        .highlight_style(theme.highlight_style())
+        .highlight_symbol("▶ ");
+    f.render_stateful_widget(list, layout[0], &mut state);
+
+    let footer_block = Block::default()
+        .borders(ratatui::widgets::Borders::TOP)
+        .border_style(Style::default().fg(theme.subtle))
+        .style(Style::default().bg(theme.background))
+        .padding(ratatui::widgets::Padding::new(1, 1, 0, 0));
+
+    let footer = Paragraph::new(
+        Line::from(
+            vec![
+                Span::styled("  [", theme.muted_text()),
+                Span::styled("↑↓", Style::default().fg(theme.accent)),
+                Span::styled("] Scroll  ", theme.muted_text()),
+                Span::styled("[", theme.muted_text()),
+                Span::styled("Enter", Style::default().fg(theme.credit)),
+                Span::styled("] Apply All  ", theme.muted_text()),
+                Span::styled("[", theme.muted_text()),
+                Span::styled("Esc", Style::default().fg(theme.debit)),
+                Span::styled("] Cancel", theme.muted_text())
+            ]
+        )
+    ).block(footer_block);
+    f.render_widget(footer, layout[1]);
+}
+
+fn diff_line(label: &str, before: &str, after: &str, theme: &Theme) -> Line<'static> {
+    Line::from(
+        vec![
+            Span::styled(format!("      {:<7}", label), theme.muted_text()),
+            Span::styled(
+                before.to_string(),
+                Style::default().fg(theme.debit).add_modifier(Modifier::CROSSED_OUT)
+            ),
+            Span::styled(" → ", theme.muted_text()),
+            Span::styled(after.to_string(), Style::default().fg(theme.credit))
+        ]
+    )
+}
+
+// ============================================================================
+// Rules input handler
+// ============================================================================
+
+pub fn handle_rules(app: &mut App, key: KeyCode, conn: &Connection) -> bool {
+    match key {
+        KeyCode::Esc => {
+            app.rule_changes.clear();
+            app.mode = Mode::Normal;
+        }
+        KeyCode::Up if app.rule_selected > 0 => {
+            app.rule_selected -= 1;
+        }
+        KeyCode::Down if app.rule_selected + 1 < app.rule_changes.len() => {
+            app.rule_selected += 1;
+        }
+        KeyCode::Enter => {
+            match apply_changes(conn, &app.rule_changes, &app.hooks) {
+                Ok(updated) => {
+                    app.notify(format!("Rules updated {} transactions", updated.value), NotificationLevel::Info);
+                    app.report_hook_errors(&updated.warnings);
+                }
+                Err(e) =>
+                    app.notify(
+                        format!("Applying rules failed, nothing was changed: {}", e),
+                        NotificationLevel::Warning
+                    ),
+            }
+            app.rule_changes.clear();
+            app.mode = Mode::Normal;
+            app.refresh(conn);
+        }
+        _ => {}
+    }
+
+    false
+}
@@ -0,0 +1,679 @@
+use std::collections::HashMap;
+
+use crossterm::event::KeyCode;
+use ratatui::{ prelude::*, widgets::{ BarChart, Block, Gauge, LineGauge, Paragraph } };
+
+use crate::{ app::App, budget::BudgetReport, models::Tag, stats::StatsSnapshot, theme::Theme };
+
+use super::{ goals, reports };
+
+// ============================================================================
+// Stats UI rendering functions
+// ============================================================================
+
+pub fn draw_stats_view(f: &mut Frame, app: &App, snapshot: &StatsSnapshot, theme: &Theme) {
+    let currency = app.currency.as_str();
+    let monthly_history = &snapshot.monthly_history;
+    let top_tags = &snapshot.top_tags;
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(1), Constraint::Length(3)])
+        .split(f.size());
+
+    // Split main stats area into top charts, budget gauges and bottom breakdown
+    let budget_height = if snapshot.budget.is_empty() {
+        0
+    } else {
+        // Borders + overall gauge + one line per tag, capped to keep room for details
+        (snapshot.budget.tags.len() as u16).min(6) + if snapshot.budget.overall.is_some() { 3 } else { 2 }
+    };
+
+    let top_bottom = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(12),
+            Constraint::Length(budget_height),
+            Constraint::Min(1),
+        ])
+        .split(layout[0]);
+
+    let charts_area = top_bottom[0];
+    let breakdown_area = top_bottom[2];
+
+    if !snapshot.budget.is_empty() {
+        draw_budget_gauges(f, top_bottom[1], &snapshot.budget, theme, currency);
+    }
+
+    // Charts area: left = monthly history, right = top tags
+    let cols = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(charts_area);
+
+    // Prepare monthly bars and sparkline
+    let mut month_labels: Vec<String> = Vec::new();
+    let mut earned_vals: Vec<u64> = Vec::new();
+    let mut spent_vals: Vec<u64> = Vec::new();
+    for (m, e, s) in monthly_history.iter().rev() {
+        month_labels.push(m.clone());
+        earned_vals.push((*e).round().abs() as u64);
+        spent_vals.push((*s).round().abs() as u64);
+    }
+
+    // Labels as &str for BarChart
+    let month_label_refs: Vec<&str> = month_labels
+        .iter()
+        .map(|s| s.as_str())
+        .collect();
+
+    // Monthly earned bar chart
+    let monthly_earned: Vec<(&str, u64)> = month_label_refs
+        .iter()
+        .zip(earned_vals.iter())
+        .map(|(l, v)| (*l, *v))
+        .collect();
+
+    let max_month = earned_vals.iter().chain(spent_vals.iter()).copied().max().unwrap_or(0);
+
+    let earned_chart = BarChart::default()
+        .data(&monthly_earned)
+        .block(Block::default().title("Monthly Earned").borders(ratatui::widgets::Borders::ALL))
+        .max(max_month.max(1))
+        .bar_width(7)
+        .bar_gap(1)
+        .bar_style(Style::default().fg(theme.credit));
+
+    f.render_widget(earned_chart, cols[0]);
+
+    // Right column: top tags bar chart
+    let mut tag_labels: Vec<String> = Vec::new();
+    let mut tag_vals: Vec<u64> = Vec::new();
+    for (t, v) in top_tags.iter().take(6) {
+        tag_labels.push(t.as_str().to_string());
+        tag_vals.push((*v).round().abs() as u64);
+    }
+    let tag_label_refs: Vec<&str> = tag_labels
+        .iter()
+        .map(|s| s.as_str())
+        .collect();
+    let tag_bars: Vec<(&str, u64)> = tag_label_refs
+        .iter()
+        .zip(tag_vals.iter())
+        .map(|(l, v)| (*l, *v))
+        .collect();
+
+    let max_tag = tag_vals.iter().copied().max().unwrap_or(0);
+
+    let tags_chart = BarChart::default()
+        .data(&tag_bars)
+        .block(Block::default().title("Top Tags").borders(ratatui::widgets::Borders::ALL))
+        .max(max_tag.max(1))
+        .bar_width(6)
+        .bar_gap(1)
+        .bar_style(Style::default().fg(theme.debit));
+
+    f.render_widget(tags_chart, cols[1]);
+
+    // Below charts: the built-in details, or a custom report script picked with ←/→
+    match app.report_index.checked_sub(1).and_then(|i| app.reports.get(i)) {
+        Some(report) => reports::draw_report(f, breakdown_area, report, theme),
+        None => {
+            let breakdown_lines = build_stats_content(snapshot, theme, currency);
+            let breakdown = Paragraph::new(breakdown_lines)
+                .block(theme.block("Details"))
+                .alignment(Alignment::Left);
+
+            f.render_widget(breakdown, breakdown_area);
+        }
+    }
+
+    // Enhanced footer
+    let footer_block = Block::default()
+        .borders(ratatui::widgets::Borders::TOP)
+        .border_style(Style::default().fg(theme.subtle))
+        .style(Style::default().bg(theme.background))
+        .padding(ratatui::widgets::Padding::new(1, 1, 0, 0));
+
+    let mut hints = Vec::new();
+    if !app.reports.is_empty() {
+        hints.extend([
+            Span::styled("  [", theme.muted_text()),
+            Span::styled("←→", Style::default().fg(theme.accent)),
+            Span::styled(format!("] Reports ({}/{})  ", app.report_index, app.reports.len()), theme.muted_text()),
+        ]);
+    }
+    hints.extend([
+        Span::styled("  [", theme.muted_text()),
+        Span::styled("r", Style::default().fg(theme.accent)),
+        Span::styled("] Reload Scripts  ", theme.muted_text()),
+        Span::styled("[", theme.muted_text()),
+        Span::styled("Esc", Style::default().fg(theme.debit)),
+        Span::styled("] Back to Main View", theme.muted_text()),
+    ]);
+
+    let footer = Paragraph::new(Line::from(hints))
+        .block(footer_block)
+        .alignment(Alignment::Left);
+
+    f.render_widget(footer, layout[1]);
+}
+
+fn draw_budget_gauges(
+    f: &mut Frame,
+    area: Rect,
+    report: &BudgetReport,
+    theme: &Theme,
+    currency: &str
+) {
+    let block = theme.block(" Budgets ").title(
+        Line::styled(format!(" {} ", report.month), theme.muted_text()).alignment(Alignment::Right)
+    );
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let mut constraints = Vec::new();
+    if report.overall.is_some() {
+        constraints.push(Constraint::Length(1));
+    }
+    constraints.extend(report.tags.iter().take(6).map(|_| Constraint::Length(1)));
+    constraints.push(Constraint::Min(0));
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(inner);
+
+    let mut row = 0;
+
+    if let Some(overall) = &report.overall {
+        let color = theme.budget_color(overall.ratio());
+        let per_day = report
+            .remaining_per_day()
+            .map(|v| format!("  ·  {}{:.2}/day left", currency, v))
+            .unwrap_or_default();
+
+        let gauge = Gauge::default()
+            .gauge_style(Style::default().fg(color).bg(theme.surface))
+            .ratio(overall.ratio().clamp(0.0, 1.0))
+            .label(
+                format!(
+                    "Overall {}{:.2} / {}{:.2} ({:.0}%){}",
+                    currency,
+                    overall.spent,
+                    currency,
+                    overall.limit,
+                    overall.ratio() * 100.0,
+                    per_day
+                )
+            );
+        f.render_widget(gauge, rows[row]);
+        row += 1;
+    }
+
+    for status in report.tags.iter().take(6) {
+        let color = theme.budget_color(status.ratio());
+        let gauge = LineGauge::default()
+            .ratio(status.ratio().clamp(0.0, 1.0))
+            .label(
+                format!(
+                    "{:<13} {}{:>9.2} / {}{:<9.2} {:>4.0}%",
+                    status.label(),
+                    currency,
+                    status.spent,
+                    currency,
+                    status.limit,
+                    status.ratio() * 100.0
+                )
+            )
+            .style(Style::default().fg(theme.foreground))
+            .gauge_style(Style::default().fg(color).bg(theme.subtle));
+        f.render_widget(gauge, rows[row]);
+        row += 1;
+    }
+}
+
+fn build_stats_content(snapshot: &StatsSnapshot, theme: &Theme, currency: &str) -> Vec<Line<'static>> {
+    let earned = snapshot.earned;
+    let spent = snapshot.spent;
+    let balance = snapshot.balance;
+    let per_tag = &snapshot.per_tag;
+    let monthly_history = &snapshot.monthly_history;
+    let tx_count = snapshot.tx_count;
+    let largest = snapshot.largest.clone();
+    let smallest = snapshot.smallest.clone();
+    let top_tags = &snapshot.top_tags;
+
+    let mut lines = Vec::new();
+
+    lines.push(Line::raw(""));
+    lines.extend(create_overview_section(earned, spent, balance, theme, currency));
+    lines.push(Line::raw(""));
+
+    if !snapshot.budget.is_empty() {
+        lines.extend(create_budget_section(&snapshot.budget, theme, currency));
+        lines.push(Line::raw(""));
+    }
+
+    if !snapshot.goals.is_empty() {
+        lines.extend(goals::create_goals_section(&snapshot.goals, theme, currency));
+        lines.push(Line::raw(""));
+    }
+
+    lines.push(
+        Line::styled(
+            "  ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━",
+            Style::default().fg(theme.subtle)
+        )
+    );
+    lines.push(Line::raw(""));
+
+    // Quick stats summary
+    lines.push(
+        Line::styled(
+            format!(
+                "  Transactions: {}  |  Total Earned: {}{:.2}  |  Total Spent: {}{:.2}",
+                tx_count,
+                currency,
+                earned,
+                currency,
+                spent
+            ),
+            Style::default().fg(theme.muted)
+        )
+    );
+    lines.push(Line::raw(""));
+
+    // Monthly history mini-table
+    lines.push(
+        Line::styled(
+            "  Last Months (YYYY-MM)  Earned      Spent",
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+        )
+    );
+    lines.push(Line::raw(""));
+    if monthly_history.is_empty() {
+        lines.push(
+            Line::styled(
+                "     No monthly data available.",
+                Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC)
+            )
+        );
+    } else {
+        for (m, e, s) in monthly_history {
+            lines.push(
+                Line::from(
+                    vec![
+                        Span::raw("     "),
+                        Span::styled(format!("{:<7}", m), Style::default().fg(theme.foreground)),
+                        Span::raw("  "),
+                        Span::styled(
+                            format!("{}{:>9.2}", currency, e),
+                            Style::default().fg(theme.credit)
+                        ),
+                        Span::raw("  "),
+                        Span::styled(
+                            format!("{}{:>9.2}", currency, s),
+                            Style::default().fg(theme.debit)
+                        )
+                    ]
+                )
+            );
+        }
+    }
+
+    lines.push(Line::raw(""));
+
+    // Top tags
+    lines.push(
+        Line::styled(
+            "  Top Spending Categories",
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+        )
+    );
+    lines.push(Line::raw(""));
+    if top_tags.is_empty() {
+        lines.push(
+            Line::styled(
+                "     No category data.",
+                Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC)
+            )
+        );
+    } else {
+        for (i, (tag, amt)) in top_tags.iter().take(5).enumerate() {
+            lines.push(
+                Line::from(
+                    vec![
+                        Span::raw("     "),
+                        Span::styled(
+                            format!("{}. #{:<12}", i + 1, tag.as_str()),
+                            Style::default().fg(theme.foreground)
+                        ),
+                        Span::raw("  "),
+                        Span::styled(
+                            format!("{}{:>9.2}", currency, amt),
+                            Style::default().fg(theme.debit)
+                        )
+                    ]
+                )
+            );
+        }
+    }
+
+    lines.push(Line::raw(""));
+
+    // Largest / Smallest transactions
+    lines.push(
+        Line::styled(
+            "  Notable Transactions",
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+        )
+    );
+    lines.push(Line::raw(""));
+    if let Some(tx) = largest {
+        lines.push(
+            Line::from(
+                vec![
+                    Span::raw("     Largest: "),
+                    Span::styled(
+                        format!(
+                            "{} | {}{:.2} | #{}",
+                            tx.source,
+                            currency,
+                            tx.amount,
+                            tx.tag.as_str()
+                        ),
+                        Style::default().fg(theme.foreground)
+                    )
+                ]
+            )
+        );
+    }
+    if let Some(tx) = smallest {
+        lines.push(
+            Line::from(
+                vec![
+                    Span::raw("     Smallest: "),
+                    Span::styled(
+                        format!(
+                            "{} | {}{:.2} | #{}",
+                            tx.source,
+                            currency,
+                            tx.amount,
+                            tx.tag.as_str()
+                        ),
+                        Style::default().fg(theme.foreground)
+                    )
+                ]
+            )
+        );
+    }
+
+    lines.push(Line::raw(""));
+    lines.push(
+        Line::styled(
+            "  ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━",
+            Style::default().fg(theme.subtle)
+        )
+    );
+
+    lines.push(Line::raw(""));
+    lines.push(
+        Line::styled(
+            "  📊 Spending Breakdown by Category",
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+        )
+    );
+    lines.push(Line::raw(""));
+
+    if per_tag.is_empty() {
+        lines.push(
+            Line::styled(
+                "     No spending data available yet.",
+                Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC)
+            )
+        );
+    } else {
+        lines.extend(create_tag_breakdown_section(per_tag, &snapshot.budget, theme, currency));
+    }
+
+    lines.push(Line::raw(""));
+    lines
+}
+
+fn create_overview_section(
+    earned: f64,
+    spent: f64,
+    balance: f64,
+    theme: &Theme,
+    currency: &str
+) -> Vec<Line<'static>> {
+    let balance_color = if balance >= 0.0 { theme.credit } else { theme.debit };
+    let savings_rate = if earned > 0.0 {
+        (((earned - spent) / earned) * 100.0).max(0.0)
+    } else {
+        0.0
+    };
+
+    vec![
+        Line::styled(
+            "  💰 Financial Overview",
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+        ),
+        Line::raw(""),
+        Line::from(
+            vec![
+                Span::raw("     Total Earned  : "),
+                Span::styled(
+                    format!("{}{:>10.2}", currency, earned),
+                    Style::default().fg(theme.credit).add_modifier(Modifier::BOLD)
+                )
+            ]
+        ),
+        Line::from(
+            vec![
+                Span::raw("     Total Spent   : "),
+                Span::styled(
+                    format!("{}{:>10.2}", currency, spent),
+                    Style::default().fg(theme.debit).add_modifier(Modifier::BOLD)
+                )
+            ]
+        ),
+        Line::from(
+            vec![
+                Span::raw("     Balance       : "),
+                Span::styled(
+                    format!("{}{:>10.2}", currency, balance),
+                    Style::default()
+                        .fg(balance_color)
+                        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+                )
+            ]
+        ),
+        Line::from(
+            vec![
+                Span::raw("     Savings Rate  : "),
+                Span::styled(
+                    format!("{:>9.1}%", savings_rate),
+                    Style::default()
+                        .fg(if savings_rate > 20.0 { theme.credit } else { theme.accent })
+                        .add_modifier(Modifier::BOLD)
+                )
+            ]
+        )
+    ]
+}
+
+fn create_budget_section(report: &BudgetReport, theme: &Theme, currency: &str) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::styled(
+            format!("  🎯 Budget for {}", report.month),
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+        ),
+        Line::raw("")
+    ];
+
+    if let Some(overall) = &report.overall {
+        let color = theme.budget_color(overall.ratio());
+        lines.push(
+            Line::from(
+                vec![
+                    Span::raw("     Monthly Budget: "),
+                    Span::styled(
+                        format!("{}{:>10.2}", currency, overall.limit),
+                        Style::default().fg(theme.foreground).add_modifier(Modifier::BOLD)
+                    )
+                ]
+            )
+        );
+        lines.push(
+            Line::from(
+                vec![
+                    Span::raw("     Remaining     : "),
+                    Span::styled(
+                        format!("{}{:>10.2}", currency, overall.remaining()),
+                        Style::default().fg(color).add_modifier(Modifier::BOLD)
+                    )
+                ]
+            )
+        );
+    }
+
+    if let Some(per_day) = report.remaining_per_day() {
+        lines.push(
+            Line::from(
+                vec![
+                    Span::raw("     Per Day Left  : "),
+                    Span::styled(
+                        format!("{}{:>10.2}", currency, per_day),
+                        Style::default().fg(theme.foreground)
+                    ),
+                    Span::styled(format!("  ({} days left)", report.days_left), theme.muted_text())
+                ]
+            )
+        );
+    }
+
+    let over: Vec<String> = report.tags
+        .iter()
+        .filter(|s| s.ratio() >= 1.0)
+        .map(|s| s.label())
+        .collect();
+    if !over.is_empty() {
+        lines.push(
+            Line::styled(format!("     Over budget   : {}", over.join(", ")), theme.danger())
+        );
+    }
+
+    lines
+}
+
+fn create_tag_breakdown_section(
+    per_tag: &HashMap<Tag, f64>,
+    report: &BudgetReport,
+    theme: &Theme,
+    currency: &str
+) -> Vec<Line<'static>> {
+    let mut tag_vec: Vec<_> = per_tag.iter().collect();
+    tag_vec.sort_by(|a, b| b.1.partial_cmp(a.1).unwrap());
+
+    let max_spent = tag_vec
+        .first()
+        .map(|(_, v)| **v)
+        .unwrap_or(0.0);
+    let total_spent: f64 = per_tag.values().sum();
+
+    let mut lines = Vec::new();
+
+    for (tag, &amount) in tag_vec {
+        let percentage = if total_spent > 0.0 { (amount / total_spent) * 100.0 } else { 0.0 };
+
+        let mut line = create_tag_bar(tag.as_str(), amount, percentage, max_spent, theme, currency);
+
+        // Budget column: this month's spending against the tag's monthly limit
+        if let Some(status) = report.for_tag(tag) {
+            line.spans.push(Span::raw("  "));
+            line.spans.push(
+                Span::styled(
+                    format!(
+                        "{}{:.0}/{}{:.0} this month",
+                        currency,
+                        status.spent,
+                        currency,
+                        status.limit
+                    ),
+                    Style::default().fg(theme.budget_color(status.ratio()))
+                )
+            );
+        }
+
+        lines.push(line);
+    }
+
+    lines
+}
+
+fn create_tag_bar(
+    tag: &str,
+    amount: f64,
+    percentage: f64,
+    max_amount: f64,
+    theme: &Theme,
+    currency: &str
+) -> Line<'static> {
+    let bar_width = calculate_bar_width(amount, max_amount);
+    let bar = "█".repeat(bar_width);
+    let empty_bar = "░".repeat((20usize).saturating_sub(bar_width));
+
+    Line::from(
+        vec![
+            Span::raw("     "),
+            Span::styled(
+                format!("#{:<12}", tag),
+                Style::default().fg(theme.accent_soft).add_modifier(Modifier::ITALIC)
+            ),
+            Span::raw(" "),
+            Span::styled(bar, Style::default().fg(theme.debit)),
+            Span::styled(empty_bar, Style::default().fg(theme.subtle)),
+            Span::raw("  "),
+            Span::styled(
+                format!("{}{:>9.2}", currency, amount),
+                Style::default().fg(theme.foreground).add_modifier(Modifier::BOLD)
+            ),
+            Span::raw(" "),
+            Span::styled(format!("({:>5.1}%)", percentage), Style::default().fg(theme.muted))
+        ]
+    )
+}
+
+fn calculate_bar_width(amount: f64, max_amount: f64) -> usize {
+    if max_amount > 0.0 { ((amount / max_amount) * 20.0).round() as usize } else { 0 }
+}
+
+// ============================================================================
+// Stats input handler
+// ============================================================================
+
+pub fn handle_stats(app: &mut App, key: KeyCode) -> bool {
+    // Index 0 is the built-in details panel, 1.. are report scripts
+    let pages = app.reports.len() + 1;
+
+    match key {
+        KeyCode::Esc => {
+            app.mode = crate::app::Mode::Normal;
+        }
+        KeyCode::Right => {
+            app.report_index = (app.report_index + 1) % pages;
+        }
+        KeyCode::Left => {
+            app.report_index = (app.report_index + pages - 1) % pages;
+        }
+        KeyCode::Char('r') => {
+            app.run_reports();
+        }
+        _ => {}
+    }
+
+    false
+}
@@ -0,0 +1,119 @@
+use crossterm::event::KeyCode;
+use ratatui::{ prelude::*, widgets::{ Block, List, ListItem, ListState, Paragraph } };
+use rusqlite::Connection;
+
+use crate::{ app::{ App, Mode }, theme::Theme };
+
+// ============================================================================
+// Suggestions UI rendering functions
+// ============================================================================
+
+pub fn draw_suggestions_view(f: &mut Frame, app: &App, theme: &Theme) {
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(1), Constraint::Length(3)])
+        .split(f.size());
+
+    let items: Vec<ListItem> = if app.suggestions.is_empty() {
+        vec![
+            ListItem::new(
+                Line::styled(
+                    "  No repeating payments found that aren't already recurring.",
+                    Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC)
+                )
+            )
+        ]
+    } else {
+        app.suggestions
+            .iter()
+            .map(|s| {
+                ListItem::new(
+                    Line::from(
+                        vec![
+                            Span::raw("  "),
+                            Span::styled(
+                                s.describe(&app.currency),
+                                Style::default().fg(theme.foreground)
+                            ),
+                            Span::raw("  "),
+                            Span::styled(
+                                format!("({} payments)", s.occurrences),
+                                theme.muted_text()
+                            ),
+                            Span::raw(" "),
+                            Span::styled(
+                                format!("#{}", s.tag.as_str()),
+                                Style::default()
+                                    .fg(theme.accent_soft)
+                                    .add_modifier(Modifier::ITALIC)
+                            )
+                        ]
+                    )
+                )
+            })
+            .collect()
+    };
+
+    let mut state = ListState::default();
+    if !app.suggestions.is_empty() {
+        state.select(Some(app.suggestion_selected));
+    }
+
+    let list = List::new(items)
+        .block(theme.block(" Suggested Recurring Entries "))
+        .highlight_style(theme.highlight_style())
+        .highlight_symbol("▶ ");
+
+    f.render_stateful_widget(list, layout[0], &mut state);
+
+    let footer_block = Block::default()
+        .borders(ratatui::widgets::Borders::TOP)
+        .border_style(Style::default().fg(theme.subtle))
+        .style(Style::default().bg(theme.background))
+        .padding(ratatui::widgets::Padding::new(1, 1, 0, 0));
+
+    let footer = Paragraph::new(
+        Line::from(
+            vec![
+                Span::styled("  [", theme.muted_text()),
+                Span::styled("↑↓", Style::default().fg(theme.accent)),
+                Span::styled("] Navigate  ", theme.muted_text()),
+                Span::styled("[", theme.muted_text()),
+                Span::styled("Enter", Style::default().fg(theme.credit)),
+                Span::styled("] Make Recurring  ", theme.muted_text()),
+                Span::styled("[", theme.muted_text()),
+                Span::styled("x", Style::default().fg(theme.debit)),
+                Span::styled("] Dismiss  ", theme.muted_text()),
+                Span::styled("[", theme.muted_text()),
+                Span::styled("Esc", Style::default().fg(theme.subtle)),
+                Span::styled("] Back", theme.muted_text())
+            ]
+        )
+    ).block(footer_block);
+
+    f.render_widget(footer, layout[1]);
+}
+
+// ============================================================================
+// Suggestions input handler
+// ============================================================================
+
+pub fn handle_suggestions(app: &mut App, key: KeyCode, conn: &Connection) -> bool {
+    match key {
+        KeyCode::Esc => {
+            app.mode = Mode::Normal;
+        }
+        KeyCode::Up if app.suggestion_selected > 0 => {
+            app.suggestion_selected -= 1;
+        }
+        KeyCode::Down if app.suggestion_selected + 1 < app.suggestions.len() => {
+            app.suggestion_selected += 1;
+        }
+        KeyCode::Enter => app.accept_suggestion(conn),
+        KeyCode::Char('x') => app.dismiss_suggestion(),
+        _ => {}
+    }
+
+    false
+}